@@ -14,6 +14,7 @@ pub use writable::WritableStream;
 
 pub(crate) mod queuing_strategy;
 pub mod readable;
+pub(crate) mod timer;
 pub mod transform;
 pub(crate) mod util;
 pub mod writable;
@@ -1,3 +1,5 @@
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
 pub mod sys;
 
 #[derive(Debug)]
@@ -12,6 +14,15 @@ impl QueuingStrategy {
         Self { raw }
     }
 
+    /// Creates a strategy that measures the size of each chunk by its `byteLength`.
+    pub fn new_byte_length(high_water_mark: f64) -> Self {
+        let init = sys::QueuingStrategyInit::new(high_water_mark);
+        let raw = sys::ByteLengthQueuingStrategy::new(&init)
+            .unwrap_throw()
+            .unchecked_into();
+        Self { raw }
+    }
+
     #[inline]
     pub fn into_raw(self) -> web_sys::QueuingStrategy {
         self.raw
@@ -0,0 +1,163 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use futures_util::stream::{Stream, StreamExt};
+use js_sys::{Array, TypeError};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::IntoStream;
+
+/// Splits a chunk from the source into a chunk for each branch.
+type SplitFn = fn(JsValue) -> Result<[JsValue; 2], JsValue>;
+
+/// The state shared between both branches of a bounded tee.
+struct Shared {
+    source: Option<IntoStream<'static>>,
+    split: SplitFn,
+    queues: [VecDeque<Result<JsValue, JsValue>>; 2],
+    wakers: [Option<Waker>; 2],
+    canceled: [bool; 2],
+    done: bool,
+    max_lag: usize,
+}
+
+impl Shared {
+    fn wake(&mut self, index: usize) {
+        if let Some(waker) = self.wakers[index].take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A branch for the [`tee_with_backpressure`](super::ReadableStream::tee_with_backpressure)
+/// and [`unzip`](super::ReadableStream::unzip) methods.
+///
+/// Each chunk read from the source by one branch is split into a chunk for each branch,
+/// and the other branch's chunk is queued for that branch.
+/// A branch stops reading from the source while the other branch's queue is full.
+pub(super) struct BoundedTeeBranch {
+    shared: Rc<RefCell<Shared>>,
+    index: usize,
+}
+
+pub(super) fn bounded_tee(
+    source: IntoStream<'static>,
+    max_lag: usize,
+) -> (BoundedTeeBranch, BoundedTeeBranch) {
+    assert!(max_lag > 0, "max_lag must be greater than zero");
+    bounded_split(source, max_lag, |chunk| Ok([chunk.clone(), chunk]))
+}
+
+pub(super) fn unzip(source: IntoStream<'static>) -> (BoundedTeeBranch, BoundedTeeBranch) {
+    bounded_split(source, 1, |chunk| {
+        let pair = chunk
+            .dyn_into::<Array>()
+            .ok()
+            .filter(|pair| pair.length() == 2)
+            .ok_or_else(|| TypeError::new("expected a chunk with 2 elements"))?;
+        Ok([pair.get(0), pair.get(1)])
+    })
+}
+
+fn bounded_split(
+    source: IntoStream<'static>,
+    max_lag: usize,
+    split: SplitFn,
+) -> (BoundedTeeBranch, BoundedTeeBranch) {
+    let shared = Rc::new(RefCell::new(Shared {
+        source: Some(source),
+        split,
+        queues: Default::default(),
+        wakers: Default::default(),
+        canceled: [false; 2],
+        done: false,
+        max_lag,
+    }));
+    (
+        BoundedTeeBranch {
+            shared: shared.clone(),
+            index: 0,
+        },
+        BoundedTeeBranch { shared, index: 1 },
+    )
+}
+
+impl Stream for BoundedTeeBranch {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (index, other) = (self.index, 1 - self.index);
+        let mut shared = self.shared.borrow_mut();
+        let shared = &mut *shared;
+        if let Some(item) = shared.queues[index].pop_front() {
+            // Made room in our queue, the other branch may continue reading
+            shared.wake(other);
+            return Poll::Ready(Some(item));
+        }
+        if shared.done {
+            return Poll::Ready(None);
+        }
+        if !shared.canceled[other] && shared.queues[other].len() >= shared.max_lag {
+            // The other branch is lagging behind, wait for it to catch up
+            shared.wakers[index] = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        // The source is only dropped once both branches are canceled
+        let source = shared.source.as_mut().unwrap_throw();
+        match source.poll_next_unpin(cx) {
+            Poll::Ready(Some(item)) => {
+                let [first, second] = match item.and_then(shared.split) {
+                    Ok([first, second]) => [Ok(first), Ok(second)],
+                    Err(err) => {
+                        // The source errored, or its chunk could not be split
+                        shared.done = true;
+                        shared.source = None;
+                        [Err(err.clone()), Err(err)]
+                    }
+                };
+                let (ours, theirs) = if index == 0 {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                if !shared.canceled[other] {
+                    shared.queues[other].push_back(theirs);
+                }
+                shared.wake(other);
+                Poll::Ready(Some(ours))
+            }
+            Poll::Ready(None) => {
+                shared.done = true;
+                shared.source = None;
+                shared.wake(other);
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                // Only the most recent waker is kept by the source,
+                // so we also need to wake this branch when the other one reads a chunk.
+                shared.wakers[index] = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for BoundedTeeBranch {
+    fn drop(&mut self) {
+        let (index, other) = (self.index, 1 - self.index);
+        let mut shared = self.shared.borrow_mut();
+        shared.canceled[index] = true;
+        shared.queues[index].clear();
+        if shared.canceled[other] {
+            // Both branches are canceled, cancel the source
+            shared.source = None;
+        } else {
+            // The other branch no longer needs to wait for us
+            shared.wake(other);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
 
 use js_sys::{Object, Uint8Array};
 use wasm_bindgen::{JsCast, JsValue};
@@ -84,7 +85,13 @@ impl<'stream> ReadableStreamBYOBReader<'stream> {
     /// Therefore, it is necessary to use a separate buffer living in the JavaScript heap.
     /// To avoid repeated allocations for repeated reads,
     /// use [`read_with_buffer`](Self::read_with_buffer).
+    ///
+    /// If `dst` is empty, this returns `Ok(0)` right away without reading from the stream.
     pub async fn read(&mut self, dst: &mut [u8]) -> Result<usize, JsValue> {
+        if dst.is_empty() {
+            // Nothing to read into, so don't bother allocating a buffer or reading from the stream
+            return Ok(0);
+        }
         let buffer = Uint8Array::new_with_length(clamp_to_u32(dst.len()));
         let (bytes_read, _) = self.read_with_buffer(dst, buffer).await?;
         Ok(bytes_read)
@@ -142,6 +149,37 @@ impl<'stream> ReadableStreamBYOBReader<'stream> {
         Ok((filled_len, Some(new_buffer)))
     }
 
+    /// Reads the next chunk of at most `len` bytes from the stream's internal queue,
+    /// and returns the filled view together with the `done` flag of the read result.
+    ///
+    /// * If some bytes were read, this returns `Ok((view, false))`.
+    /// * If the stream closes and no more bytes are available, this returns `Ok((view, true))`,
+    ///   where `view` is empty.
+    /// * If the stream cancels, this returns `Ok((view, true))`, where `view` is a new
+    ///   empty `Uint8Array`.
+    /// * If the stream encounters an `error`, this returns `Err(error)`.
+    ///
+    /// Unlike [`read`](Self::read), the bytes are not copied into WebAssembly memory.
+    /// This avoids a copy when the bytes only need to be processed in JavaScript, or only need
+    /// to be inspected transiently. Use [`Uint8Array::to_vec`] or [`Uint8Array::copy_to`]
+    /// to copy the bytes afterwards if needed.
+    pub async fn read_view(&mut self, len: usize) -> Result<(Uint8Array, bool), JsValue> {
+        let view = Uint8Array::new_with_length(clamp_to_u32(len)).unchecked_into::<Object>();
+        let promise = self.as_raw().read_with_array_buffer_view(&view);
+        let js_result = JsFuture::from(promise).await?;
+        let result = sys::ReadableStreamReadResult::from(js_result);
+        let done = result.get_done().unwrap_or_default();
+        let js_value = result.get_value();
+        let filled_view = if js_value.is_undefined() {
+            // No new view was returned. The stream must have been canceled.
+            assert!(done);
+            Uint8Array::new_with_length(0)
+        } else {
+            js_value.unchecked_into::<Uint8Array>()
+        };
+        Ok((filled_view, done))
+    }
+
     /// [Releases](https://streams.spec.whatwg.org/#release-a-lock) this reader's lock on the
     /// corresponding stream.
     ///
@@ -181,6 +219,18 @@ impl<'stream> ReadableStreamBYOBReader<'stream> {
             .map_err(|err| (err, self))
     }
 
+    /// Consumes this reader without [releasing](https://streams.spec.whatwg.org/#release-a-lock)
+    /// its lock, returning the underlying [JavaScript reader](sys::ReadableStreamBYOBReader).
+    ///
+    /// The stream remains locked to the returned raw reader. This can be used to hand the reader
+    /// over to JavaScript code, which then becomes responsible for releasing the lock.
+    pub fn forget(self) -> sys::ReadableStreamBYOBReader {
+        // Skip our destructor, so the lock is not released
+        let mut this = ManuallyDrop::new(self);
+        // Move the raw reader out, leaving a placeholder that does not own a JS value
+        mem::replace(&mut this.raw, JsValue::UNDEFINED.unchecked_into())
+    }
+
     /// Converts this `ReadableStreamBYOBReader` into an [`AsyncRead`].
     ///
     /// This is similar to [`ReadableStream.into_async_read`](ReadableStream::into_async_read),
@@ -0,0 +1,83 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::Cell;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+use futures_util::ready;
+use futures_util::stream::{Stream, StreamExt};
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use super::IntoStream;
+
+/// A handle to the checksum of a stream,
+/// as returned by [`with_checksum`](super::ReadableStream::with_checksum).
+#[derive(Debug, Clone)]
+pub struct ChecksumHandle {
+    digest: Rc<Cell<Option<u64>>>,
+}
+
+impl ChecksumHandle {
+    /// Returns the final digest of all bytes in the stream.
+    ///
+    /// This returns `None` until the stream has closed. If the stream errors
+    /// or is canceled instead, this keeps returning `None`.
+    pub fn digest(&self) -> Option<u64> {
+        self.digest.get()
+    }
+}
+
+/// A [`Stream`] for the [`with_checksum`](super::ReadableStream::with_checksum) method.
+pub(super) struct Checksum<H> {
+    stream: IntoStream<'static>,
+    hasher: Option<H>,
+    digest: Rc<Cell<Option<u64>>>,
+}
+
+impl<H: Hasher> Checksum<H> {
+    pub fn new(stream: IntoStream<'static>, hasher: H) -> (Self, ChecksumHandle) {
+        let digest = Rc::new(Cell::new(None));
+        let handle = ChecksumHandle {
+            digest: digest.clone(),
+        };
+        let checksum = Self {
+            stream,
+            hasher: Some(hasher),
+            digest,
+        };
+        (checksum, handle)
+    }
+}
+
+// The hasher is never pinned.
+impl<H> Unpin for Checksum<H> {}
+
+impl<H: Hasher> Stream for Checksum<H> {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let result = ready!(this.stream.poll_next_unpin(cx));
+        match &result {
+            Some(Ok(chunk)) => {
+                if let (Some(hasher), Some(chunk)) =
+                    (&mut this.hasher, chunk.dyn_ref::<Uint8Array>())
+                {
+                    hasher.write(&chunk.to_vec());
+                }
+            }
+            Some(Err(_)) => {
+                // The stream errored, the digest will never be complete.
+                this.hasher = None;
+            }
+            None => {
+                if let Some(hasher) = this.hasher.take() {
+                    this.digest.set(Some(hasher.finish()));
+                }
+            }
+        }
+        Poll::Ready(result)
+    }
+}
@@ -0,0 +1,91 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::timer::{sleep, Sleep};
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`chunks_timeout`](super::ReadableStream::chunks_timeout) method.
+///
+/// Buffers chunks from the wrapped stream, and emits them as an `Array` once either
+/// `max_count` chunks are buffered, or `max_ms` milliseconds have passed since the first
+/// chunk was buffered.
+pub(super) struct ChunksTimeout {
+    stream: Option<IntoStream<'static>>,
+    buffer: Vec<JsValue>,
+    timer: Option<Sleep>,
+    error: Option<JsValue>,
+    max_count: usize,
+    max_ms: u32,
+}
+
+impl ChunksTimeout {
+    pub fn new(stream: IntoStream<'static>, max_count: usize, max_ms: u32) -> Self {
+        assert!(max_count > 0, "max_count must be greater than zero");
+        Self {
+            stream: Some(stream),
+            buffer: Vec::with_capacity(max_count),
+            timer: None,
+            error: None,
+            max_count,
+            max_ms,
+        }
+    }
+
+    fn flush(&mut self) -> JsValue {
+        self.timer = None;
+        self.buffer.drain(..).collect::<Array>().into()
+    }
+}
+
+impl Stream for ChunksTimeout {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let stream = match this.stream.as_mut() {
+                Some(stream) => stream,
+                None => {
+                    // Source has ended, emit any remaining chunks before ending or erroring
+                    if !this.buffer.is_empty() {
+                        return Poll::Ready(Some(Ok(this.flush())));
+                    }
+                    return Poll::Ready(this.error.take().map(Err));
+                }
+            };
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    if this.buffer.is_empty() {
+                        // Start the timer on the first buffered chunk
+                        this.timer = Some(sleep(this.max_ms));
+                    }
+                    this.buffer.push(chunk);
+                    if this.buffer.len() >= this.max_count {
+                        return Poll::Ready(Some(Ok(this.flush())));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.error = Some(err);
+                    this.stream = None;
+                }
+                Poll::Ready(None) => {
+                    this.stream = None;
+                }
+                Poll::Pending => {
+                    if let Some(timer) = this.timer.as_mut() {
+                        if timer.poll_unpin(cx).is_ready() {
+                            return Poll::Ready(Some(Ok(this.flush())));
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Default)]
+struct CloseState {
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+/// A handle to close a stream early,
+/// as returned by [`from_stream_closable`](super::ReadableStream::from_stream_closable).
+#[derive(Debug, Clone)]
+pub struct CloseHandle {
+    state: Rc<RefCell<CloseState>>,
+}
+
+impl CloseHandle {
+    /// Closes the stream, without draining the remaining items of the Rust stream.
+    ///
+    /// Readers see the end of the stream on their next read, including any read that is
+    /// currently pending. Unlike
+    /// [cancelling](https://streams.spec.whatwg.org/#cancel-a-readable-stream), this is done
+    /// from the producer side, and the readable stream becomes closed rather than errored.
+    ///
+    /// The Rust stream is not dropped right away, but only once the readable stream
+    /// next pulls from it. If a read is currently pending, this happens as soon as
+    /// that read is woken up.
+    pub fn close(&self) {
+        let mut state = self.state.borrow_mut();
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`Stream`] for the [`from_stream_closable`](super::ReadableStream::from_stream_closable)
+/// method.
+pub(super) struct Closable<St> {
+    stream: Option<Pin<Box<St>>>,
+    state: Rc<RefCell<CloseState>>,
+}
+
+impl<St> Closable<St> {
+    pub fn new(stream: St) -> (Self, CloseHandle) {
+        let state = Rc::new(RefCell::new(CloseState::default()));
+        let handle = CloseHandle {
+            state: state.clone(),
+        };
+        let stream = Closable {
+            stream: Some(Box::pin(stream)),
+            state,
+        };
+        (stream, handle)
+    }
+}
+
+impl<St> Stream for Closable<St>
+where
+    St: Stream<Item = Result<JsValue, JsValue>>,
+{
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.closed {
+                drop(state);
+                self.stream = None;
+                return Poll::Ready(None);
+            }
+            state.waker = Some(cx.waker().clone());
+        }
+        match self.stream.as_mut() {
+            Some(stream) => stream.poll_next_unpin(cx),
+            None => Poll::Ready(None),
+        }
+    }
+}
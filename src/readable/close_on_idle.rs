@@ -0,0 +1,63 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use wasm_bindgen::prelude::*;
+
+use crate::timer::{sleep, Sleep};
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`close_on_idle`](super::ReadableStream::close_on_idle) method.
+///
+/// Ends as soon as the wrapped stream has not produced a chunk for `idle_ms` milliseconds,
+/// dropping (and thereby canceling) the wrapped stream.
+pub(super) struct CloseOnIdle {
+    stream: Option<IntoStream<'static>>,
+    timer: Option<Sleep>,
+    idle_ms: u32,
+}
+
+impl CloseOnIdle {
+    pub fn new(stream: IntoStream<'static>, idle_ms: u32) -> Self {
+        Self {
+            stream: Some(stream),
+            timer: None,
+            idle_ms,
+        }
+    }
+}
+
+impl Stream for CloseOnIdle {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let stream = match this.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(None),
+        };
+        match stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(item)) => {
+                // Restart the timer on the next poll
+                this.timer = None;
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                this.stream = None;
+                this.timer = None;
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                let idle_ms = this.idle_ms;
+                let timer = this.timer.get_or_insert_with(|| sleep(idle_ms));
+                futures_util::ready!(timer.poll_unpin(cx));
+                // Idle for too long, cancel the source and close
+                this.stream = None;
+                this.timer = None;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use wasm_bindgen::prelude::*;
+
+use crate::timer::{sleep, Sleep};
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`debounce`](super::ReadableStream::debounce) method.
+///
+/// Keeps only the latest chunk from the wrapped stream, and emits it once no new chunk
+/// has arrived for `quiet_ms` milliseconds.
+pub(super) struct Debounce {
+    stream: Option<IntoStream<'static>>,
+    latest: Option<JsValue>,
+    timer: Option<Sleep>,
+    error: Option<JsValue>,
+    quiet_ms: u32,
+}
+
+impl Debounce {
+    pub fn new(stream: IntoStream<'static>, quiet_ms: u32) -> Self {
+        Self {
+            stream: Some(stream),
+            latest: None,
+            timer: None,
+            error: None,
+            quiet_ms,
+        }
+    }
+}
+
+impl Stream for Debounce {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let stream = match this.stream.as_mut() {
+                Some(stream) => stream,
+                None => {
+                    // Source has ended, emit the latest chunk right away before ending or erroring
+                    if let Some(chunk) = this.latest.take() {
+                        this.timer = None;
+                        return Poll::Ready(Some(Ok(chunk)));
+                    }
+                    return Poll::Ready(this.error.take().map(Err));
+                }
+            };
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    // Replace the previous chunk and restart the timer
+                    this.latest = Some(chunk);
+                    this.timer = Some(sleep(this.quiet_ms));
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.error = Some(err);
+                    this.stream = None;
+                }
+                Poll::Ready(None) => {
+                    this.stream = None;
+                }
+                Poll::Pending => {
+                    if let Some(timer) = this.timer.as_mut() {
+                        if timer.poll_unpin(cx).is_ready() {
+                            this.timer = None;
+                            return Poll::Ready(this.latest.take().map(Ok));
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
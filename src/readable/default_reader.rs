@@ -1,10 +1,15 @@
 use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop};
 
+use futures_util::future::{select, Either};
+use futures_util::FutureExt;
+use js_sys::{TypeError, Uint8Array};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::util::promise_to_void_future;
+use crate::timer::sleep;
+use crate::util::{checked_cast_to_usize, clamp_to_u32, promise_to_void_future};
 
 use super::{sys, IntoStream, ReadableStream};
 
@@ -17,6 +22,8 @@ use super::{sys, IntoStream, ReadableStream};
 #[derive(Debug)]
 pub struct ReadableStreamDefaultReader<'stream> {
     raw: sys::ReadableStreamDefaultReader,
+    pending_read: Option<JsFuture>,
+    pending_bytes: Option<Uint8Array>,
     _stream: PhantomData<&'stream mut ReadableStream>,
 }
 
@@ -28,6 +35,8 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
                 .unchecked_ref::<sys::ReadableStreamExt>()
                 .try_get_reader()?
                 .unchecked_into(),
+            pending_read: None,
+            pending_bytes: None,
             _stream: PhantomData,
         })
     }
@@ -52,6 +61,8 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
     ///
     /// Equivalent to [`ReadableStream.cancel`](ReadableStream::cancel).
     pub async fn cancel(&mut self) -> Result<(), JsValue> {
+        // Any pending read is resolved by the cancellation, so it no longer needs to be continued
+        self.pending_read = None;
         promise_to_void_future(self.as_raw().cancel()).await
     }
 
@@ -60,6 +71,7 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
     ///
     /// Equivalent to [`ReadableStream.cancel_with_reason`](ReadableStream::cancel_with_reason).
     pub async fn cancel_with_reason(&mut self, reason: &JsValue) -> Result<(), JsValue> {
+        self.pending_read = None;
         promise_to_void_future(self.as_raw().cancel_with_reason(reason)).await
     }
 
@@ -68,10 +80,12 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
     /// * If a next `chunk` becomes available, this returns `Ok(Some(chunk))`.
     /// * If the stream closes and no more chunks are available, this returns `Ok(None)`.
     /// * If the stream encounters an `error`, this returns `Err(error)`.
+    ///
+    /// If a previous read was interrupted (for example, because its future was dropped or
+    /// because [`read_timeout`](Self::read_timeout) timed out), then this continues that read
+    /// instead of starting a new one, so no chunk is lost.
     pub async fn read(&mut self) -> Result<Option<JsValue>, JsValue> {
-        let promise = self.as_raw().read();
-        let js_result = JsFuture::from(promise).await?;
-        let result = sys::ReadableStreamReadResult::from(js_result);
+        let result = self.read_result().await?;
         if result.get_done().unwrap_or_default() {
             Ok(None)
         } else {
@@ -79,6 +93,126 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
         }
     }
 
+    /// Reads the next chunk from the stream's internal queue,
+    /// returning both the read value and the `done` flag of the read result.
+    ///
+    /// * If a next `chunk` becomes available, this returns `Ok((Some(chunk), false))`.
+    /// * If the stream closes, this returns `Ok((value, true))`, where `value` is `None`
+    ///   if the read result's value is `undefined`.
+    /// * If the stream encounters an `error`, this returns `Err(error)`.
+    ///
+    /// Unlike [`read`](Self::read), this does not discard the value that accompanies a
+    /// `done` read result. Note that streams following the Streams standard always report
+    /// an `undefined` value for a default reader once they are done, but custom reader
+    /// implementations may not.
+    pub async fn read_full(&mut self) -> Result<(Option<JsValue>, bool), JsValue> {
+        let result = self.read_result().await?;
+        let done = result.get_done().unwrap_or_default();
+        let value = result.get_value();
+        if done && value.is_undefined() {
+            Ok((None, true))
+        } else {
+            Ok((Some(value), done))
+        }
+    }
+
+    async fn read_result(&mut self) -> Result<sys::ReadableStreamReadResult, JsValue> {
+        let raw = &self.raw;
+        let fut = self
+            .pending_read
+            .get_or_insert_with(|| JsFuture::from(raw.read()));
+        let js_result = fut.await;
+        self.pending_read = None;
+        Ok(sys::ReadableStreamReadResult::from(js_result?))
+    }
+
+    /// Reads bytes from the stream into `dst`, and returns the number of bytes read.
+    ///
+    /// The stream must produce [`Uint8Array`] chunks. Each chunk is copied into `dst` as far as
+    /// it fits, and any remaining bytes are kept by this reader for the next call.
+    /// This allows reading bytes from a stream that is not a readable byte stream,
+    /// without having to deal with chunk boundaries.
+    ///
+    /// * If some bytes were read into `dst`, this returns `Ok(bytes_read)`.
+    /// * If the stream closes and no more bytes are available, or if `dst` is empty,
+    ///   this returns `Ok(0)`.
+    /// * If the stream produces a chunk that is not a `Uint8Array`, this returns a `TypeError`.
+    /// * If the stream encounters an `error`, this returns `Err(error)`.
+    pub async fn read_bytes(&mut self, dst: &mut [u8]) -> Result<usize, JsValue> {
+        if dst.is_empty() {
+            return Ok(0);
+        }
+        let bytes = loop {
+            if let Some(bytes) = self.pending_bytes.take() {
+                break bytes;
+            }
+            match self.read().await? {
+                Some(chunk) => {
+                    let chunk = chunk
+                        .dyn_into::<Uint8Array>()
+                        .map_err(|_| TypeError::new("expected a Uint8Array chunk"))?;
+                    // Skip empty chunks, since returning 0 would signal the end of the stream
+                    if chunk.length() > 0 {
+                        self.pending_bytes = Some(chunk);
+                    }
+                }
+                None => return Ok(0),
+            }
+        };
+        let len = clamp_to_u32(dst.len()).min(bytes.length());
+        bytes
+            .subarray(0, len)
+            .copy_to(&mut dst[0..checked_cast_to_usize(len)]);
+        if len < bytes.length() {
+            // Keep the remaining bytes for the next call
+            self.pending_bytes = Some(bytes.subarray(len, bytes.length()));
+        }
+        Ok(checked_cast_to_usize(len))
+    }
+
+    /// Takes the bytes that were already read from the stream by [`read_bytes`](Self::read_bytes),
+    /// but were not yet returned because they did not fit in `dst`.
+    ///
+    /// These bytes are only kept by this reader, so they are lost when the reader is
+    /// [released](Self::release_lock) or [forgotten](Self::forget). Call this first
+    /// to hand them over together with the rest of the stream.
+    #[inline]
+    pub fn take_pending_bytes(&mut self) -> Option<Uint8Array> {
+        self.pending_bytes.take()
+    }
+
+    /// Returns `true` if this reader has an outstanding read request.
+    ///
+    /// This is the case when a previous read was interrupted before it completed, for example
+    /// because its future was dropped or because [`read_timeout`](Self::read_timeout) timed out.
+    /// Such a read is continued by the next call to [`read`](Self::read).
+    ///
+    /// This can be checked before calling [`try_release_lock`](Self::try_release_lock),
+    /// since older Streams implementations do not allow releasing a lock while there are
+    /// pending read requests.
+    #[inline]
+    pub fn has_pending_reads(&self) -> bool {
+        self.pending_read.is_some()
+    }
+
+    /// Reads the next chunk from the stream's internal queue,
+    /// giving up if no chunk becomes available within `ms` milliseconds.
+    ///
+    /// * If a next `chunk` becomes available in time, this returns `Ok(Some(chunk))`.
+    /// * If the stream closes and no more chunks are available, this returns `Ok(None)`.
+    /// * If the stream encounters an `error`, this returns `Err(Some(error))`.
+    /// * If the deadline expires, this returns `Err(None)`.
+    ///
+    /// When the deadline expires, the underlying read request is kept alive by this reader.
+    /// The next call to [`read`](Self::read) or `read_timeout` picks it up again,
+    /// so the reader remains usable and no chunk is lost.
+    pub async fn read_timeout(&mut self, ms: u32) -> Result<Option<JsValue>, Option<JsValue>> {
+        match select(self.read().boxed_local(), sleep(ms)).await {
+            Either::Left((result, _)) => result.map_err(Some),
+            Either::Right(((), _)) => Err(None),
+        }
+    }
+
     /// [Releases](https://streams.spec.whatwg.org/#release-a-lock) this reader's lock on the
     /// corresponding stream.
     ///
@@ -118,6 +252,26 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
             .map_err(|err| (err, self))
     }
 
+    /// Consumes this reader without [releasing](https://streams.spec.whatwg.org/#release-a-lock)
+    /// its lock, returning the underlying [JavaScript reader](sys::ReadableStreamDefaultReader).
+    ///
+    /// The stream remains locked to the returned raw reader. This can be used to hand the reader
+    /// over to JavaScript code, which then becomes responsible for releasing the lock.
+    ///
+    /// Any bytes left over from [`read_bytes`](Self::read_bytes) are discarded, since the
+    /// returned raw reader cannot see them. Use [`take_pending_bytes`](Self::take_pending_bytes)
+    /// to retrieve them beforehand.
+    pub fn forget(self) -> sys::ReadableStreamDefaultReader {
+        // Skip our destructor, so the lock is not released
+        let mut this = ManuallyDrop::new(self);
+        // Drop any interrupted read, since it can no longer be continued
+        this.pending_read = None;
+        // Discard any leftover bytes, see above
+        this.pending_bytes = None;
+        // Move the raw reader out, leaving a placeholder that does not own a JS value
+        mem::replace(&mut this.raw, JsValue::UNDEFINED.unchecked_into())
+    }
+
     /// Converts this `ReadableStreamDefaultReader` into a [`Stream`].
     ///
     /// This is similar to [`ReadableStream.into_stream`](ReadableStream::into_stream),
@@ -130,6 +284,11 @@ impl<'stream> ReadableStreamDefaultReader<'stream> {
     pub fn into_stream(self) -> IntoStream<'stream> {
         IntoStream::new(self, false)
     }
+
+    #[inline]
+    pub(super) fn take_pending_read(&mut self) -> Option<JsFuture> {
+        self.pending_read.take()
+    }
 }
 
 impl Drop for ReadableStreamDefaultReader<'_> {
@@ -0,0 +1,75 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use futures_util::stream::Stream;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, EventTarget};
+
+/// A [`Stream`] for the [`from_events`](super::ReadableStream::from_events) method.
+///
+/// Events are buffered as soon as the stream is created, so no events are lost
+/// between creating the stream and the first read.
+pub(super) struct EventStream {
+    target: EventTarget,
+    event_type: String,
+    shared: Rc<RefCell<Shared>>,
+    on_event: Closure<dyn FnMut(Event)>,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: VecDeque<Event>,
+    waker: Option<Waker>,
+}
+
+impl EventStream {
+    pub fn new(target: &EventTarget, event_type: &str) -> Self {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let on_event = Closure::new({
+            let shared = shared.clone();
+            move |event: Event| {
+                let mut shared = shared.borrow_mut();
+                shared.queue.push_back(event);
+                if let Some(waker) = shared.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+        target
+            .add_event_listener_with_callback(event_type, on_event.as_ref().unchecked_ref())
+            .unwrap_throw();
+        Self {
+            target: target.clone(),
+            event_type: event_type.to_string(),
+            shared,
+            on_event,
+        }
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+        if let Some(event) = shared.queue.pop_front() {
+            return Poll::Ready(Some(Ok(event.into())));
+        }
+        shared.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        // Detach the listener before its closure is dropped
+        let _ = self.target.remove_event_listener_with_callback(
+            &self.event_type,
+            self.on_event.as_ref().unchecked_ref(),
+        );
+    }
+}
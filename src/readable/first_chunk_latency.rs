@@ -0,0 +1,70 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures_util::ready;
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use crate::timer::now;
+
+use super::IntoStream;
+
+/// A handle to the time-to-first-chunk of a stream,
+/// as returned by [`into_stream_timed`](super::ReadableStream::into_stream_timed).
+#[derive(Debug, Clone)]
+pub struct FirstChunkLatency {
+    latency: Rc<Cell<Option<f64>>>,
+}
+
+impl FirstChunkLatency {
+    /// Returns the time in milliseconds between the creation of the stream
+    /// and the completion of its first read.
+    ///
+    /// This returns `None` until the first chunk has been read. If the stream closes
+    /// or errors before producing any chunk, this keeps returning `None`.
+    pub fn get(&self) -> Option<f64> {
+        self.latency.get()
+    }
+}
+
+/// A [`Stream`] for the [`into_stream_timed`](super::ReadableStream::into_stream_timed) method.
+pub(super) struct Timed {
+    stream: IntoStream<'static>,
+    // The start time, until the first chunk is seen
+    start: Option<f64>,
+    latency: Rc<Cell<Option<f64>>>,
+}
+
+impl Timed {
+    pub fn new(stream: IntoStream<'static>) -> (Self, FirstChunkLatency) {
+        let latency = Rc::new(Cell::new(None));
+        let handle = FirstChunkLatency {
+            latency: latency.clone(),
+        };
+        let timed = Self {
+            stream,
+            start: Some(now()),
+            latency,
+        };
+        (timed, handle)
+    }
+}
+
+impl Stream for Timed {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.stream.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(_)) => {
+                if let Some(start) = self.start.take() {
+                    self.latency.set(Some(now() - start));
+                }
+            }
+            _ => self.start = None,
+        }
+        Poll::Ready(item)
+    }
+}
@@ -0,0 +1,221 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::rc::Rc;
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use super::{IntoStream, ReadableStream};
+
+/// The state of a single group.
+#[derive(Default)]
+struct Group {
+    queue: VecDeque<Result<JsValue, JsValue>>,
+    waker: Option<Waker>,
+    canceled: bool,
+}
+
+/// The state shared between the stream of groups and all of its sub-streams.
+struct Shared<K, F> {
+    source: Option<IntoStream<'static>>,
+    key: F,
+    groups: Vec<Group>,
+    indices: HashMap<K, usize>,
+    // Groups that were created, but not yet emitted by the stream of groups
+    new_groups: VecDeque<(K, usize)>,
+    waker: Option<Waker>,
+    canceled: bool,
+    done: bool,
+}
+
+impl<K, F> Shared<K, F> {
+    fn cancel_group(&mut self, index: usize) {
+        self.groups[index] = Group {
+            canceled: true,
+            ..Group::default()
+        };
+    }
+
+    fn wake_all(&mut self) {
+        let wakers = self.groups.iter_mut().map(|group| &mut group.waker);
+        for waker in wakers.chain([&mut self.waker]) {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn cancel_if_unused(&mut self) {
+        if self.canceled && self.groups.iter().all(|group| group.canceled) {
+            // Nobody is reading anymore, cancel the source
+            self.source = None;
+        }
+    }
+}
+
+impl<K, F> Shared<K, F>
+where
+    K: Eq + Hash + Clone,
+    F: FnMut(&JsValue) -> K,
+{
+    /// Reads the next item from the source, and dispatches it to its group.
+    fn poll_source(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        // The source is only dropped once the source is done, or once everything is canceled
+        let source = self.source.as_mut().unwrap_throw();
+        match source.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                let key = (self.key)(&chunk);
+                match self.indices.get(&key) {
+                    Some(&index) => {
+                        let group = &mut self.groups[index];
+                        if !group.canceled {
+                            group.queue.push_back(Ok(chunk));
+                        }
+                    }
+                    None if !self.canceled => {
+                        let index = self.groups.len();
+                        let mut group = Group::default();
+                        group.queue.push_back(Ok(chunk));
+                        self.groups.push(group);
+                        self.indices.insert(key.clone(), index);
+                        self.new_groups.push_back((key, index));
+                    }
+                    None => {
+                        // Nobody can receive a new group anymore, drop the chunk
+                    }
+                }
+            }
+            Poll::Ready(Some(Err(err))) => {
+                for group in self.groups.iter_mut().filter(|group| !group.canceled) {
+                    group.queue.push_back(Err(err.clone()));
+                }
+                self.done = true;
+                self.source = None;
+            }
+            Poll::Ready(None) => {
+                self.done = true;
+                self.source = None;
+            }
+            Poll::Pending => return Poll::Pending,
+        }
+        // Only the most recent waker is kept by the source,
+        // so every other reader needs to check again whether it can make progress.
+        self.wake_all();
+        Poll::Ready(())
+    }
+}
+
+/// A [`Stream`] for the [`group_by`](super::ReadableStream::group_by) method.
+///
+/// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+pub(super) struct GroupBy<K, F> {
+    shared: Rc<RefCell<Shared<K, F>>>,
+}
+
+impl<K, F> GroupBy<K, F> {
+    pub fn new(source: IntoStream<'static>, key: F) -> Self {
+        let shared = Rc::new(RefCell::new(Shared {
+            source: Some(source),
+            key,
+            groups: Vec::new(),
+            indices: HashMap::new(),
+            new_groups: VecDeque::new(),
+            waker: None,
+            canceled: false,
+            done: false,
+        }));
+        Self { shared }
+    }
+}
+
+impl<K, F> Stream for GroupBy<K, F>
+where
+    K: Eq + Hash + Clone + 'static,
+    F: FnMut(&JsValue) -> K + 'static,
+{
+    type Item = (K, ReadableStream);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (key, index) = {
+            let mut shared = self.shared.borrow_mut();
+            loop {
+                if let Some(new_group) = shared.new_groups.pop_front() {
+                    break new_group;
+                }
+                if shared.done {
+                    return Poll::Ready(None);
+                }
+                if shared.poll_source(cx).is_pending() {
+                    shared.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+        let branch = GroupBranch {
+            shared: self.shared.clone(),
+            index,
+        };
+        Poll::Ready(Some((key, ReadableStream::from_stream(branch))))
+    }
+}
+
+impl<K, F> Drop for GroupBy<K, F> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.canceled = true;
+        // Groups that were never emitted can no longer be read
+        while let Some((_, index)) = shared.new_groups.pop_front() {
+            shared.cancel_group(index);
+        }
+        shared.waker = None;
+        shared.cancel_if_unused();
+        // We may have been the last one to poll the source,
+        // so the remaining readers need to poll again to register their own wakers.
+        shared.wake_all();
+    }
+}
+
+/// A sub-stream for a single group of the [`group_by`](super::ReadableStream::group_by) method.
+struct GroupBranch<K, F> {
+    shared: Rc<RefCell<Shared<K, F>>>,
+    index: usize,
+}
+
+impl<K, F> Stream for GroupBranch<K, F>
+where
+    K: Eq + Hash + Clone,
+    F: FnMut(&JsValue) -> K,
+{
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let index = self.index;
+        let mut shared = self.shared.borrow_mut();
+        loop {
+            if let Some(item) = shared.groups[index].queue.pop_front() {
+                return Poll::Ready(Some(item));
+            }
+            if shared.done {
+                return Poll::Ready(None);
+            }
+            if shared.poll_source(cx).is_pending() {
+                shared.groups[index].waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl<K, F> Drop for GroupBranch<K, F> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.cancel_group(self.index);
+        shared.cancel_if_unused();
+        // We may have been the last one to poll the source,
+        // so the remaining readers need to poll again to register their own wakers.
+        shared.wake_all();
+    }
+}
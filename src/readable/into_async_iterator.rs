@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures_util::future::{abortable, AbortHandle, TryFutureExt};
+use futures_util::stream::{Stream, TryStreamExt};
+use js_sys::{Object, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
+
+use crate::util::capture_panic;
+
+type JsValueStream = dyn Stream<Item = Result<JsValue, JsValue>>;
+
+/// A JavaScript [async iterator](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols#the_async_iterator_and_async_iterable_protocols)
+/// over a Rust stream, for the
+/// [`from_stream_as_async_iterable`](super::ReadableStream::from_stream_as_async_iterable)
+/// method.
+#[wasm_bindgen]
+pub(crate) struct IntoAsyncIterator {
+    stream: Rc<RefCell<Option<Pin<Box<JsValueStream>>>>>,
+    next_handle: Option<AbortHandle>,
+}
+
+impl IntoAsyncIterator {
+    pub fn new(stream: Box<JsValueStream>) -> Self {
+        IntoAsyncIterator {
+            stream: Rc::new(RefCell::new(Some(stream.into()))),
+            next_handle: None,
+        }
+    }
+}
+
+#[allow(clippy::await_holding_refcell_ref)]
+#[wasm_bindgen]
+impl IntoAsyncIterator {
+    pub fn next(&mut self) -> Promise {
+        let stream = self.stream.clone();
+        let fut = capture_panic(async move {
+            // This mutable borrow can never panic, since ReadableStream.from()
+            // waits for each call to next() to settle before calling it again.
+            let mut stream = stream.try_borrow_mut().unwrap_throw();
+            let result = match stream.as_mut() {
+                Some(inner) => inner.try_next().await,
+                None => Ok(None),
+            };
+            match result {
+                Ok(Some(chunk)) => Ok(iterator_result(&chunk, false)),
+                Ok(None) => {
+                    // The stream has closed, drop it.
+                    *stream = None;
+                    Ok(iterator_result(&JsValue::undefined(), true))
+                }
+                Err(err) => {
+                    // The stream encountered an error, drop it.
+                    *stream = None;
+                    Err(err)
+                }
+            }
+        });
+
+        // Allow aborting the future from return().
+        let (fut, handle) = abortable(fut);
+        let stream = self.stream.clone();
+        let fut = fut.unwrap_or_else(move |_| {
+            // The aborted future has already been dropped, so the stream
+            // is no longer borrowed and can be dropped as well.
+            if let Ok(mut stream) = stream.try_borrow_mut() {
+                *stream = None;
+            }
+            Ok(iterator_result(&JsValue::undefined(), true))
+        });
+
+        self.next_handle = Some(handle);
+        future_to_promise(fut)
+    }
+
+    #[wasm_bindgen(js_name = "return")]
+    pub fn return_(&mut self, value: JsValue) -> Promise {
+        // The consumer has lost interest, drop the stream.
+        // If a call to next() is still pending, abort it. The stream is then dropped
+        // once the aborted call has released its borrow.
+        if let Some(handle) = self.next_handle.take() {
+            handle.abort();
+        }
+        if let Ok(mut stream) = self.stream.try_borrow_mut() {
+            *stream = None;
+        }
+        Promise::resolve(&iterator_result(&value, true))
+    }
+}
+
+impl Drop for IntoAsyncIterator {
+    fn drop(&mut self) {
+        // Abort the pending call to next(), if any.
+        if let Some(handle) = self.next_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+fn iterator_result(value: &JsValue, done: bool) -> JsValue {
+    let result = Object::new();
+    Reflect::set(&result, &"value".into(), value).unwrap_throw();
+    Reflect::set(&result, &"done".into(), &done.into()).unwrap_throw();
+    result.into()
+}
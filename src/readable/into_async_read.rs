@@ -9,7 +9,7 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::util::{checked_cast_to_usize, clamp_to_u32, js_to_io_error};
+use crate::util::{checked_cast_to_usize, clamp_to_u32, js_to_named_io_error};
 
 use super::sys::ReadableStreamReadResult;
 use super::ReadableStreamBYOBReader;
@@ -20,6 +20,11 @@ use super::ReadableStreamBYOBReader;
 /// When this `AsyncRead` is dropped, it also drops its reader which in turn
 /// [releases its lock](https://streams.spec.whatwg.org/#release-a-lock).
 ///
+/// Read errors are never retried. A read only fails once the stream has become
+/// [errored](https://streams.spec.whatwg.org/#readablestream-errored), and every later read
+/// would fail with the same error. To recover from transient errors, retry at the source
+/// instead, for example by re-creating the stream.
+///
 /// [`AsyncRead`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncRead.html
 #[must_use = "readers do nothing unless polled"]
 #[derive(Debug)]
@@ -28,6 +33,12 @@ pub struct IntoAsyncRead<'reader> {
     buffer: Option<Uint8Array>,
     fut: Option<JsFuture>,
     cancel_on_drop: bool,
+    drop_cancel_reason: Option<JsValue>,
+    name: Option<String>,
+    min_read_size: usize,
+    leftover: Vec<u8>,
+    leftover_pos: usize,
+    last_js_error: Option<JsValue>,
 }
 
 impl<'reader> IntoAsyncRead<'reader> {
@@ -38,9 +49,88 @@ impl<'reader> IntoAsyncRead<'reader> {
             buffer: None,
             fut: None,
             cancel_on_drop,
+            drop_cancel_reason: None,
+            name: None,
+            min_read_size: 0,
+            leftover: Vec::new(),
+            leftover_pos: 0,
+            last_js_error: None,
         }
     }
 
+    /// Sets the minimum number of bytes to request from the stream for each read.
+    ///
+    /// By default, every read on the stream is sized to the caller's buffer. When reading
+    /// with small buffers, this leads to many round-trips to JavaScript. With a minimum read
+    /// size, this `AsyncRead` instead reads at least `size` bytes at once, and serves any bytes
+    /// that don't fit in the caller's buffer from an internal buffer on subsequent reads.
+    pub fn with_min_read_size(mut self, size: usize) -> Self {
+        self.min_read_size = size;
+        self
+    }
+
+    /// Immediately starts reading up to `size` bytes from the stream, before this `AsyncRead`
+    /// is first polled.
+    ///
+    /// This allows the underlying source to start producing bytes while the caller is still
+    /// busy with other work, so the first [`poll_read`](AsyncRead::poll_read) can complete
+    /// sooner. Any bytes that don't fit in the caller's first buffer are kept for subsequent
+    /// reads, as with [`with_min_read_size`](Self::with_min_read_size).
+    ///
+    /// Note that the read is in flight from the moment this is called. If this `AsyncRead` is
+    /// dropped before reading those bytes, then they are lost: the stream is either
+    /// [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream)
+    /// (for [`into_async_read`](super::ReadableStream::into_async_read)),
+    /// or the pending read is rejected when the lock is
+    /// [released](https://streams.spec.whatwg.org/#release-a-lock).
+    pub fn with_prefetch(mut self, size: usize) -> Self {
+        if self.fut.is_none() && self.leftover_pos >= self.leftover.len() {
+            self.start_read(size);
+        }
+        self
+    }
+
+    /// Sets the `reason` with which the stream is
+    /// [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream) when this `AsyncRead`
+    /// is dropped before the stream has ended.
+    ///
+    /// By default, the stream is canceled without a reason (i.e. with `undefined`).
+    /// A meaningful reason, such as `"consumer dropped"`, lets the underlying source
+    /// tell why it was canceled. This has no effect if this `AsyncRead` does not cancel
+    /// the stream when dropped, for example when it was created from a reader.
+    pub fn set_drop_cancel_reason(mut self, reason: JsValue) -> Self {
+        self.drop_cancel_reason = Some(reason);
+        self
+    }
+
+    /// Sets a name for this `AsyncRead`, which is prefixed to the message of any I/O error
+    /// that it returns.
+    ///
+    /// This can help to tell streams apart when debugging a pipeline with multiple streams.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Returns the number of bytes that were already read from the stream, but not yet
+    /// returned by [`poll_read`](AsyncRead::poll_read).
+    ///
+    /// These bytes are left over from a read that returned more bytes than fit in the
+    /// caller's buffer (see [`with_min_read_size`](Self::with_min_read_size)), and are
+    /// served first by subsequent reads. They are lost when this `AsyncRead` is dropped.
+    pub fn buffered_len(&self) -> usize {
+        self.leftover.len() - self.leftover_pos
+    }
+
+    /// Returns the original JavaScript error of the last failed read, if any.
+    ///
+    /// When a read on the stream fails, [`poll_read`](AsyncRead::poll_read) converts the
+    /// JavaScript error into an [`io::Error`](std::io::Error), which only keeps its message.
+    /// This returns the raw error value instead, for example to inspect its type or properties.
+    pub fn last_js_error(&self) -> Option<&JsValue> {
+        self.last_js_error.as_ref()
+    }
+
     /// [Cancels](https://streams.spec.whatwg.org/#cancel-a-readable-stream) the stream,
     /// signaling a loss of interest in the stream by a consumer.
     pub async fn cancel(mut self) -> Result<(), JsValue> {
@@ -59,6 +149,30 @@ impl<'reader> IntoAsyncRead<'reader> {
         }
     }
 
+    /// Starts reading at most `read_len` bytes into the internal buffer,
+    /// and stores the future for the pending read.
+    ///
+    /// Returns `false` if the reader was already dropped.
+    fn start_read(&mut self, read_len: usize) -> bool {
+        let reader = match &self.reader {
+            Some(reader) => reader,
+            None => return false,
+        };
+        let read_len = clamp_to_u32(read_len);
+        let buffer = match self.buffer.take() {
+            // Re-use the internal buffer if it is large enough,
+            // otherwise allocate a new one
+            Some(buffer) if buffer.byte_length() >= read_len => buffer,
+            _ => Uint8Array::new_with_length(read_len),
+        };
+        // Limit to read size
+        let buffer = buffer.subarray(0, read_len).unchecked_into::<Object>();
+        // Read into internal buffer and store its future
+        let fut = JsFuture::from(reader.as_raw().read_with_array_buffer_view(&buffer));
+        self.fut = Some(fut);
+        true
+    }
+
     #[inline]
     fn discard_reader(mut self: Pin<&mut Self>) {
         self.reader = None;
@@ -72,33 +186,25 @@ impl<'reader> AsyncRead for IntoAsyncRead<'reader> {
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<Result<usize, Error>> {
-        let read_fut = match self.fut.as_mut() {
-            Some(fut) => fut,
-            None => {
-                // No pending read, start reading the next bytes
-                let buf_len = clamp_to_u32(buf.len());
-                let buffer = match self.buffer.take() {
-                    // Re-use the internal buffer if it is large enough,
-                    // otherwise allocate a new one
-                    Some(buffer) if buffer.byte_length() >= buf_len => buffer,
-                    _ => Uint8Array::new_with_length(buf_len),
-                };
-                // Limit to output buffer size
-                let buffer = buffer.subarray(0, buf_len).unchecked_into::<Object>();
-                match &self.reader {
-                    Some(reader) => {
-                        // Read into internal buffer and store its future
-                        let fut =
-                            JsFuture::from(reader.as_raw().read_with_array_buffer_view(&buffer));
-                        self.fut.insert(fut)
-                    }
-                    None => {
-                        // Reader was already dropped
-                        return Poll::Ready(Ok(0));
-                    }
-                }
+        if self.leftover_pos < self.leftover.len() {
+            // Serve bytes left over from a previous read first
+            let this = &mut *self;
+            let leftover = &this.leftover[this.leftover_pos..];
+            let len = leftover.len().min(buf.len());
+            buf[0..len].copy_from_slice(&leftover[0..len]);
+            this.leftover_pos += len;
+            return Poll::Ready(Ok(len));
+        }
+
+        if self.fut.is_none() {
+            // No pending read, start reading the next bytes
+            let read_len = buf.len().max(self.min_read_size);
+            if !self.start_read(read_len) {
+                // Reader was already dropped
+                return Poll::Ready(Ok(0));
             }
-        };
+        }
+        let read_fut = self.fut.as_mut().unwrap_throw();
 
         // Poll the future for the pending read
         let js_result = ready!(read_fut.poll_unpin(cx));
@@ -115,19 +221,32 @@ impl<'reader> AsyncRead for IntoAsyncRead<'reader> {
                 } else {
                     // Cannot be canceled, so view must exist
                     let filled_view = result.get_value().unchecked_into::<Uint8Array>();
-                    // Copy bytes to output buffer
                     let filled_len = checked_cast_to_usize(filled_view.byte_length());
-                    debug_assert!(filled_len <= buf.len());
-                    filled_view.copy_to(&mut buf[0..filled_len]);
+                    let read_len = if filled_len <= buf.len() {
+                        // Copy bytes to output buffer
+                        filled_view.copy_to(&mut buf[0..filled_len]);
+                        filled_len
+                    } else {
+                        // Read more than requested, keep the remaining bytes for the next read
+                        let mut bytes = std::mem::take(&mut self.leftover);
+                        bytes.resize(filled_len, 0);
+                        filled_view.copy_to(&mut bytes);
+                        buf.copy_from_slice(&bytes[0..buf.len()]);
+                        self.leftover = bytes;
+                        self.leftover_pos = buf.len();
+                        buf.len()
+                    };
                     // Re-construct internal buffer with the new ArrayBuffer
                     self.buffer = Some(Uint8Array::new(&filled_view.buffer()));
-                    Ok(filled_len)
+                    Ok(read_len)
                 }
             }
             Err(js_value) => {
                 // Error
-                self.discard_reader();
-                Err(js_to_io_error(js_value))
+                self.as_mut().discard_reader();
+                let err = js_to_named_io_error(js_value.clone(), self.name.as_deref());
+                self.last_js_error = Some(js_value);
+                Err(err)
             }
         })
     }
@@ -137,8 +256,12 @@ impl<'reader> Drop for IntoAsyncRead<'reader> {
     fn drop(&mut self) {
         if self.cancel_on_drop {
             if let Some(reader) = self.reader.take() {
+                let promise = match self.drop_cancel_reason.take() {
+                    Some(reason) => reader.as_raw().cancel_with_reason(&reason),
+                    None => reader.as_raw().cancel(),
+                };
                 let on_rejected = Closure::once(|_| {});
-                let _ = reader.as_raw().cancel().catch(&on_rejected);
+                let _ = promise.catch(&on_rejected);
                 on_rejected.forget();
             }
         }
@@ -2,13 +2,37 @@ use core::pin::Pin;
 use core::task::{Context, Poll};
 
 use futures_util::ready;
-use futures_util::stream::{FusedStream, Stream};
+use futures_util::stream::{FusedStream, Stream, StreamExt};
 use futures_util::FutureExt;
+use js_sys::ArrayBuffer;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
-use super::sys::ReadableStreamReadResult;
-use super::ReadableStreamDefaultReader;
+use super::sys::{self, ReadableStreamReadResult};
+use super::{ReadableStream, ReadableStreamDefaultReader};
+
+/// The kind of chunks in a stream, as returned by [`IntoStream::chunk_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkKind {
+    /// Binary data, such as a `Uint8Array` or an `ArrayBuffer`.
+    Bytes,
+    /// A string.
+    Text,
+    /// Any other value.
+    Other,
+}
+
+impl ChunkKind {
+    fn of(chunk: &JsValue) -> Self {
+        if chunk.is_instance_of::<ArrayBuffer>() || ArrayBuffer::is_view(chunk) {
+            ChunkKind::Bytes
+        } else if chunk.is_string() {
+            ChunkKind::Text
+        } else {
+            ChunkKind::Other
+        }
+    }
+}
 
 /// A [`Stream`] for the [`into_stream`](super::ReadableStream::into_stream) method.
 ///
@@ -23,15 +47,66 @@ pub struct IntoStream<'reader> {
     reader: Option<ReadableStreamDefaultReader<'reader>>,
     fut: Option<JsFuture>,
     cancel_on_drop: bool,
+    drop_cancel_reason: Option<JsValue>,
+    // The next item, if it was already read by chunk_kind()
+    peeked: Option<Option<Result<JsValue, JsValue>>>,
+    // The stream that is locked by the reader, if this Stream owns it
+    raw_stream: Option<sys::ReadableStream>,
 }
 
 impl<'reader> IntoStream<'reader> {
     #[inline]
-    pub(super) fn new(reader: ReadableStreamDefaultReader, cancel_on_drop: bool) -> IntoStream {
+    pub(super) fn new(mut reader: ReadableStreamDefaultReader, cancel_on_drop: bool) -> IntoStream {
+        // Continue any read that was interrupted on the reader
+        let fut = reader.take_pending_read();
         IntoStream {
             reader: Some(reader),
-            fut: None,
+            fut,
             cancel_on_drop,
+            drop_cancel_reason: None,
+            peeked: None,
+            raw_stream: None,
+        }
+    }
+
+    #[inline]
+    pub(super) fn new_owned(
+        reader: ReadableStreamDefaultReader<'static>,
+        raw_stream: sys::ReadableStream,
+    ) -> IntoStream<'static> {
+        let mut stream = IntoStream::new(reader, true);
+        stream.raw_stream = Some(raw_stream);
+        stream
+    }
+
+    /// Sets the `reason` with which the stream is
+    /// [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream) when this `Stream`
+    /// is dropped before the stream has ended.
+    ///
+    /// By default, the stream is canceled without a reason (i.e. with `undefined`).
+    /// A meaningful reason, such as `"consumer dropped"`, lets the underlying source
+    /// tell why it was canceled. This has no effect if this `Stream` does not cancel
+    /// the stream when dropped, for example when it was created from a reader.
+    pub fn set_drop_cancel_reason(mut self, reason: JsValue) -> Self {
+        self.drop_cancel_reason = Some(reason);
+        self
+    }
+
+    /// Classifies the chunks of this stream, by inspecting the next chunk.
+    ///
+    /// This is a best-effort guess, assuming that all chunks of the stream are of the same kind.
+    /// It can help generic code to decide how to handle an arbitrary stream.
+    /// The inspected chunk is not lost: it is still returned by the next read from this `Stream`.
+    ///
+    /// This returns `None` if the stream closes or errors before producing a chunk.
+    pub async fn chunk_kind(&mut self) -> Option<ChunkKind> {
+        if self.peeked.is_none() {
+            let item = self.next().await;
+            self.peeked = Some(item);
+        }
+        match &self.peeked {
+            Some(Some(Ok(chunk))) => Some(ChunkKind::of(chunk)),
+            _ => None,
         }
     }
 
@@ -54,9 +129,31 @@ impl<'reader> IntoStream<'reader> {
     }
 }
 
+impl IntoStream<'static> {
+    /// Converts this `Stream` back into a [`ReadableStream`].
+    ///
+    /// If this `Stream` was created by [`ReadableStream::into_stream`] and is not in the middle
+    /// of a read, this [releases its lock](https://streams.spec.whatwg.org/#release-a-lock) and
+    /// returns the original stream, without wrapping it in another layer. This is always the case
+    /// for a `Stream` that has not been polled yet.
+    ///
+    /// Otherwise, this falls back to [`ReadableStream::from_stream`], so that a pending read
+    /// or a chunk inspected by [`chunk_kind`](Self::chunk_kind) is not lost.
+    pub fn into_readable_stream(mut self) -> ReadableStream {
+        if self.fut.is_none() && self.peeked.is_none() && self.reader.is_some() {
+            if let Some(raw_stream) = self.raw_stream.take() {
+                // Dropping the reader releases its lock, without canceling the stream
+                self.reader = None;
+                return ReadableStream::from_raw(raw_stream);
+            }
+        }
+        ReadableStream::from_stream(self)
+    }
+}
+
 impl FusedStream for IntoStream<'_> {
     fn is_terminated(&self) -> bool {
-        self.reader.is_none() && self.fut.is_none()
+        self.reader.is_none() && self.fut.is_none() && self.peeked.is_none()
     }
 }
 
@@ -64,6 +161,9 @@ impl<'reader> Stream for IntoStream<'reader> {
     type Item = Result<JsValue, JsValue>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = self.peeked.take() {
+            return Poll::Ready(item);
+        }
         let read_fut = match self.fut.as_mut() {
             Some(fut) => fut,
             None => match &self.reader {
@@ -109,8 +209,12 @@ impl<'reader> Drop for IntoStream<'reader> {
     fn drop(&mut self) {
         if self.cancel_on_drop {
             if let Some(reader) = self.reader.take() {
+                let promise = match self.drop_cancel_reason.take() {
+                    Some(reason) => reader.as_raw().cancel_with_reason(&reason),
+                    None => reader.as_raw().cancel(),
+                };
                 let on_rejected = Closure::once(|_| {});
-                let _ = reader.as_raw().cancel().catch(&on_rejected);
+                let _ = promise.catch(&on_rejected);
                 on_rejected.forget();
             }
         }
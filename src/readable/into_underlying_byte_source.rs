@@ -7,8 +7,9 @@ use futures_util::io::{AsyncRead, AsyncReadExt};
 use js_sys::{Error as JsError, Promise, Uint8Array};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
+use web_sys::AbortSignal;
 
-use crate::util::{checked_cast_to_u32, clamp_to_usize};
+use crate::util::{capture_panic, checked_cast_to_u32, clamp_to_usize};
 
 use super::sys;
 
@@ -17,7 +18,9 @@ pub(crate) struct IntoUnderlyingByteSource {
     inner: Rc<RefCell<Inner>>,
     default_buffer_len: usize,
     controller: Option<sys::ReadableByteStreamController>,
-    pull_handle: Option<AbortHandle>,
+    pull_handle: Rc<RefCell<Option<AbortHandle>>>,
+    signal: Option<AbortSignal>,
+    abort_listener: Option<Closure<dyn FnMut()>>,
 }
 
 impl IntoUnderlyingByteSource {
@@ -26,9 +29,18 @@ impl IntoUnderlyingByteSource {
             inner: Rc::new(RefCell::new(Inner::new(async_read))),
             default_buffer_len,
             controller: None,
-            pull_handle: None,
+            pull_handle: Rc::new(RefCell::new(None)),
+            signal: None,
+            abort_listener: None,
         }
     }
+
+    /// Errors the stream when the given `signal` is aborted,
+    /// aborting any pending pull and dropping the `AsyncRead`.
+    pub fn with_signal(mut self, signal: AbortSignal) -> Self {
+        self.signal = Some(signal);
+        self
+    }
 }
 
 #[allow(clippy::await_holding_refcell_ref)]
@@ -45,24 +57,59 @@ impl IntoUnderlyingByteSource {
     }
 
     pub fn start(&mut self, controller: sys::ReadableByteStreamController) {
+        if let Some(signal) = &self.signal {
+            let inner = self.inner.clone();
+            let pull_handle = self.pull_handle.clone();
+            let controller = controller.clone();
+            let reason_signal = signal.clone();
+            let on_abort = move || {
+                // Abort the pending pull, if any.
+                if let Some(handle) = pull_handle.borrow_mut().take() {
+                    handle.abort();
+                }
+                // If there's no pending pull, we can drop the AsyncRead right away.
+                // Otherwise, it is dropped once the aborted pull completes.
+                if let Ok(mut inner) = inner.try_borrow_mut() {
+                    inner.discard();
+                }
+                controller.error_with_e(&reason_signal.reason());
+            };
+            if signal.aborted() {
+                on_abort();
+            } else {
+                let listener = Closure::<dyn FnMut()>::new(on_abort);
+                signal
+                    .add_event_listener_with_callback("abort", listener.as_ref().unchecked_ref())
+                    .unwrap_throw();
+                self.abort_listener = Some(listener);
+            }
+        }
         self.controller = Some(controller);
     }
 
     pub fn pull(&mut self, controller: sys::ReadableByteStreamController) -> Promise {
         let inner = self.inner.clone();
-        let fut = async move {
+        let fut = capture_panic(async move {
             // This mutable borrow can never panic, since the ReadableStream always queues
             // each operation on the underlying source.
             let mut inner = inner.try_borrow_mut().unwrap_throw();
             inner.pull(controller).await
-        };
+        });
 
-        // Allow aborting the future from cancel().
+        // Allow aborting the future from cancel() or from the abort signal.
         let (fut, handle) = abortable(fut);
         // Ignore errors from aborting the future.
-        let fut = fut.unwrap_or_else(|_| Ok(JsValue::undefined()));
+        let inner = self.inner.clone();
+        let fut = fut.unwrap_or_else(move |_| {
+            // The aborted future has already been dropped, so the AsyncRead
+            // is no longer borrowed and can be dropped as well.
+            if let Ok(mut inner) = inner.try_borrow_mut() {
+                inner.discard();
+            }
+            Ok(JsValue::undefined())
+        });
 
-        self.pull_handle = Some(handle);
+        *self.pull_handle.borrow_mut() = Some(handle);
         future_to_promise(fut)
     }
 
@@ -75,9 +122,14 @@ impl IntoUnderlyingByteSource {
 impl Drop for IntoUnderlyingByteSource {
     fn drop(&mut self) {
         // Abort the pending pull, if any.
-        if let Some(handle) = self.pull_handle.take() {
+        if let Some(handle) = self.pull_handle.borrow_mut().take() {
             handle.abort();
         }
+        // Stop listening for the abort signal.
+        if let (Some(signal), Some(listener)) = (&self.signal, &self.abort_listener) {
+            let _ = signal
+                .remove_event_listener_with_callback("abort", listener.as_ref().unchecked_ref());
+        }
     }
 }
 
@@ -109,6 +161,8 @@ impl Inner {
         if self.buffer.len() < request_len {
             self.buffer.resize(request_len, 0);
         }
+        // Read into our own buffer rather than into the request's view,
+        // so the read can never observe a detached or resized view.
         match async_read.read(&mut self.buffer[0..request_len]).await {
             Ok(0) => {
                 // The stream has closed, drop it.
@@ -117,17 +171,34 @@ impl Inner {
                 request.respond_with_u32(0)?;
             }
             Ok(bytes_read) => {
-                // Copy read bytes from buffer to BYOB request view
                 debug_assert!(bytes_read <= request_len);
                 let bytes_read_u32 = checked_cast_to_u32(bytes_read);
-                let dest = Uint8Array::new_with_byte_offset_and_length(
-                    &request_view.buffer(),
-                    request_view.byte_offset(),
-                    bytes_read_u32,
-                );
-                dest.copy_from(&self.buffer[0..bytes_read]);
-                // Respond to BYOB request
-                request.respond_with_u32(bytes_read_u32)?;
+                // Re-fetch the BYOB request, since the view we saw before the read
+                // may have been detached or replaced in the meantime.
+                let current = controller.byob_request().and_then(|request| {
+                    let view = request.view()?.unchecked_into::<Uint8Array>();
+                    Some((request, view))
+                });
+                match current {
+                    Some((request, view)) if view.byte_length() >= bytes_read_u32 => {
+                        // Copy read bytes from buffer to BYOB request view
+                        let dest = Uint8Array::new_with_byte_offset_and_length(
+                            &view.buffer(),
+                            view.byte_offset(),
+                            bytes_read_u32,
+                        );
+                        dest.copy_from(&self.buffer[0..bytes_read]);
+                        // Respond to BYOB request
+                        request.respond_with_u32(bytes_read_u32)?;
+                    }
+                    _ => {
+                        // The view can no longer hold the read bytes, so enqueue a copy instead.
+                        // If the view was detached, this errors the stream rather than
+                        // silently dropping or corrupting the read bytes.
+                        let chunk = Uint8Array::from(&self.buffer[0..bytes_read]);
+                        controller.enqueue_with_array_buffer_view(&chunk)?;
+                    }
+                }
             }
             Err(err) => {
                 // The stream encountered an error, drop it.
@@ -2,12 +2,15 @@ use std::cell::RefCell;
 use std::pin::Pin;
 use std::rc::Rc;
 
-use futures_util::future::{abortable, AbortHandle, TryFutureExt};
+use futures_util::future::{abortable, AbortHandle, FutureExt, TryFutureExt};
 use futures_util::stream::{Stream, TryStreamExt};
-use js_sys::Promise;
+use js_sys::{Promise, TypeError, Uint8Array};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::future_to_promise;
 
+use crate::util::capture_panic;
+
 use super::sys;
 
 type JsValueStream = dyn Stream<Item = Result<JsValue, JsValue>>;
@@ -19,12 +22,22 @@ pub(crate) struct IntoUnderlyingSource {
 }
 
 impl IntoUnderlyingSource {
-    pub fn new(stream: Box<JsValueStream>) -> Self {
+    pub fn new(stream: Box<JsValueStream>, high_water_mark: f64) -> Self {
         IntoUnderlyingSource {
-            inner: Rc::new(RefCell::new(Inner::new(stream))),
+            inner: Rc::new(RefCell::new(Inner::new(stream, high_water_mark))),
             pull_handle: None,
         }
     }
+
+    /// Splits [`Uint8Array`] chunks so that the total byte length of the stream's queue
+    /// never exceeds its high water mark. The remaining bytes of a split chunk are enqueued
+    /// by the next pull. Any other chunk errors the stream.
+    ///
+    /// This must only be used with a [`ByteLengthQueuingStrategy`](web_sys::ByteLengthQueuingStrategy).
+    pub fn with_chunk_splitting(self) -> Self {
+        self.inner.borrow_mut().split_chunks = true;
+        self
+    }
 }
 
 #[allow(clippy::await_holding_refcell_ref)]
@@ -32,12 +45,12 @@ impl IntoUnderlyingSource {
 impl IntoUnderlyingSource {
     pub fn pull(&mut self, controller: sys::ReadableStreamDefaultController) -> Promise {
         let inner = self.inner.clone();
-        let fut = async move {
+        let fut = capture_panic(async move {
             // This mutable borrow can never panic, since the ReadableStream always queues
             // each operation on the underlying source.
             let mut inner = inner.try_borrow_mut().unwrap_throw();
             inner.pull(controller).await
-        };
+        });
 
         // Allow aborting the future from cancel().
         let (fut, handle) = abortable(fut);
@@ -65,12 +78,25 @@ impl Drop for IntoUnderlyingSource {
 
 struct Inner {
     stream: Option<Pin<Box<JsValueStream>>>,
+    // The high water mark of the stream's queuing strategy,
+    // i.e. its desired size when the queue is empty
+    high_water_mark: f64,
+    // An error that was read while enqueuing a batch of chunks,
+    // to be reported once the queue has drained
+    pending_error: Option<JsValue>,
+    split_chunks: bool,
+    // The bytes of a split chunk that did not fit in the queue yet
+    remaining_bytes: Option<Uint8Array>,
 }
 
 impl Inner {
-    fn new(stream: Box<JsValueStream>) -> Self {
+    fn new(stream: Box<JsValueStream>, high_water_mark: f64) -> Self {
         Inner {
             stream: Some(stream.into()),
+            high_water_mark,
+            pending_error: None,
+            split_chunks: false,
+            remaining_bytes: None,
         }
     }
 
@@ -78,15 +104,33 @@ impl Inner {
         &mut self,
         controller: sys::ReadableStreamDefaultController,
     ) -> Result<JsValue, JsValue> {
+        if self.pending_error.is_some() {
+            // Erroring the stream discards its queue, so wait until the consumer has read
+            // all chunks that were enqueued before the error. Every read that leaves room
+            // in the queue calls pull() again, so we get another chance once it is empty.
+            if controller.desired_size().unwrap_or_default() < self.high_water_mark {
+                return Ok(JsValue::undefined());
+            }
+            // The stream encountered an error in a previous pull, drop it.
+            self.stream = None;
+            return Err(self.pending_error.take().unwrap_throw());
+        }
+        if let Some(bytes) = self.remaining_bytes.take() {
+            // Finish enqueuing a previously split chunk before reading the next one.
+            // This only leaves a remainder if the queue is full again, which ends the batch below.
+            self.enqueue_bytes(&controller, bytes)?;
+            return self.pull_ready(controller);
+        }
         // The stream should still exist, since pull() will not be called again
         // after the stream has closed or encountered an error.
         let stream = self.stream.as_mut().unwrap_throw();
         match stream.try_next().await {
-            Ok(Some(chunk)) => controller.enqueue_with_chunk(&chunk)?,
+            Ok(Some(chunk)) => self.enqueue(&controller, chunk)?,
             Ok(None) => {
                 // The stream has closed, drop it.
                 self.stream = None;
                 controller.close()?;
+                return Ok(JsValue::undefined());
             }
             Err(err) => {
                 // The stream encountered an error, drop it.
@@ -94,6 +138,67 @@ impl Inner {
                 return Err(err);
             }
         };
+        self.pull_ready(controller)
+    }
+
+    fn pull_ready(
+        &mut self,
+        controller: sys::ReadableStreamDefaultController,
+    ) -> Result<JsValue, JsValue> {
+        // While the queue wants more chunks, keep enqueuing chunks that are ready right away,
+        // to save a round-trip through pull() for each of them.
+        while controller.desired_size().unwrap_or_default() > 0.0 {
+            let stream = self.stream.as_mut().unwrap_throw();
+            match stream.try_next().now_or_never() {
+                Some(Ok(Some(chunk))) => self.enqueue(&controller, chunk)?,
+                Some(Ok(None)) => {
+                    // The stream has closed, drop it.
+                    self.stream = None;
+                    controller.close()?;
+                    break;
+                }
+                Some(Err(err)) => {
+                    // Erroring the stream now would discard the chunks we just enqueued,
+                    // so hold on to the error until they have been read.
+                    self.pending_error = Some(err);
+                    break;
+                }
+                None => break,
+            }
+        }
         Ok(JsValue::undefined())
     }
+
+    fn enqueue(
+        &mut self,
+        controller: &sys::ReadableStreamDefaultController,
+        chunk: JsValue,
+    ) -> Result<(), JsValue> {
+        if !self.split_chunks {
+            return controller.enqueue_with_chunk(&chunk);
+        }
+        let bytes = chunk
+            .dyn_into::<Uint8Array>()
+            .map_err(|_| TypeError::new("expected a Uint8Array chunk"))?;
+        self.enqueue_bytes(controller, bytes)
+    }
+
+    fn enqueue_bytes(
+        &mut self,
+        controller: &sys::ReadableStreamDefaultController,
+        bytes: Uint8Array,
+    ) -> Result<(), JsValue> {
+        // Only enqueue as many bytes as fit in the queue, and keep the rest for the next pull.
+        // The queue always has room here, since we only enqueue while its desired size
+        // is positive.
+        let desired_size = controller.desired_size().unwrap_or_default();
+        let len = bytes.length();
+        if f64::from(len) <= desired_size {
+            return controller.enqueue_with_chunk(&bytes);
+        }
+        let fit = desired_size as u32;
+        controller.enqueue_with_chunk(&bytes.subarray(0, fit))?;
+        self.remaining_bytes = Some(bytes.subarray(fit, len));
+        Ok(())
+    }
 }
@@ -0,0 +1,114 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::ready;
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+/// A [`Stream`] for the
+/// [`into_length_prefixed_frames`](super::ReadableStream::into_length_prefixed_frames) method.
+pub(super) struct LengthPrefixedFrames<St> {
+    stream: St,
+    prefix_len: usize,
+    big_endian: bool,
+    buffer: Vec<u8>,
+    // The start of the unread bytes in the buffer
+    pos: usize,
+    done: bool,
+}
+
+impl<St> LengthPrefixedFrames<St> {
+    pub fn new(stream: St, prefix_len: usize, big_endian: bool) -> Self {
+        assert!(
+            (1..=8).contains(&prefix_len),
+            "prefix length must be between 1 and 8 bytes"
+        );
+        Self {
+            stream,
+            prefix_len,
+            big_endian,
+            buffer: Vec::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Takes the first complete frame from the unread bytes, if there is one.
+    fn take_frame(&mut self) -> Result<Option<Vec<u8>>, JsValue> {
+        let unread = &self.buffer[self.pos..];
+        if unread.len() < self.prefix_len {
+            return Ok(None);
+        }
+        let prefix = &unread[0..self.prefix_len];
+        let frame_len = if self.big_endian {
+            prefix
+                .iter()
+                .fold(0u64, |len, &byte| (len << 8) | u64::from(byte))
+        } else {
+            prefix
+                .iter()
+                .rev()
+                .fold(0u64, |len, &byte| (len << 8) | u64::from(byte))
+        };
+        let frame_len = usize::try_from(frame_len)
+            .map_err(|_| js_sys::RangeError::new("frame length too large"))?;
+        let frame_end = self
+            .prefix_len
+            .checked_add(frame_len)
+            .ok_or_else(|| js_sys::RangeError::new("frame length too large"))?;
+        if unread.len() < frame_end {
+            return Ok(None);
+        }
+        let frame = unread[self.prefix_len..frame_end].to_vec();
+        // Don't move the remaining bytes for every frame,
+        // they are only moved once when more bytes are appended.
+        self.pos += frame_end;
+        Ok(Some(frame))
+    }
+
+    /// Appends bytes to the buffer, discarding any bytes that were already read.
+    fn append(&mut self, bytes: &[u8]) {
+        self.buffer.drain(0..self.pos);
+        self.pos = 0;
+        self.buffer.extend_from_slice(bytes);
+    }
+}
+
+impl<St> Stream for LengthPrefixedFrames<St>
+where
+    St: Stream<Item = Result<Vec<u8>, JsValue>> + Unpin,
+{
+    type Item = Result<Vec<u8>, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            match this.take_frame() {
+                Ok(Some(frame)) => return Poll::Ready(Some(Ok(frame))),
+                Ok(None) => {}
+                Err(err) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+            match ready!(this.stream.poll_next_unpin(cx)) {
+                Some(Ok(bytes)) => this.append(&bytes),
+                Some(Err(err)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => {
+                    this.done = true;
+                    if this.pos < this.buffer.len() {
+                        // The stream ended in the middle of a frame
+                        let err = js_sys::Error::new("truncated frame at end of stream");
+                        return Poll::Ready(Some(Err(err.into())));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use futures_util::stream::Stream;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Event, MessageEvent, MessagePort};
+
+/// A [`Stream`] for the [`from_message_port`](super::ReadableStream::from_message_port) method.
+///
+/// Messages are buffered as soon as the stream is created, so no messages are lost
+/// between creating the stream and the first read.
+pub(super) struct MessagePortStream {
+    port: MessagePort,
+    shared: Rc<RefCell<Shared>>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_message_error: Closure<dyn FnMut(MessageEvent)>,
+    on_close: Closure<dyn FnMut(Event)>,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: VecDeque<JsValue>,
+    error: Option<JsValue>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl Shared {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl MessagePortStream {
+    pub fn new(port: MessagePort) -> Self {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let on_message = Closure::new({
+            let shared = shared.clone();
+            move |event: MessageEvent| {
+                let mut shared = shared.borrow_mut();
+                shared.queue.push_back(event.data());
+                shared.wake();
+            }
+        });
+        let on_message_error = Closure::new({
+            let shared = shared.clone();
+            move |_event: MessageEvent| {
+                let mut shared = shared.borrow_mut();
+                let error = js_sys::Error::new("failed to deserialize message");
+                shared.error.get_or_insert(error.into());
+                shared.wake();
+            }
+        });
+        let on_close = Closure::new({
+            let shared = shared.clone();
+            move |_event: Event| {
+                let mut shared = shared.borrow_mut();
+                shared.closed = true;
+                shared.wake();
+            }
+        });
+        // Setting onmessage implicitly starts the port
+        port.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        port.set_onmessageerror(Some(on_message_error.as_ref().unchecked_ref()));
+        port.add_event_listener_with_callback("close", on_close.as_ref().unchecked_ref())
+            .unwrap_throw();
+        Self {
+            port,
+            shared,
+            _on_message: on_message,
+            _on_message_error: on_message_error,
+            on_close,
+        }
+    }
+}
+
+impl Stream for MessagePortStream {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+        if let Some(message) = shared.queue.pop_front() {
+            return Poll::Ready(Some(Ok(message)));
+        }
+        if let Some(error) = shared.error.take() {
+            shared.closed = true;
+            return Poll::Ready(Some(Err(error)));
+        }
+        if shared.closed {
+            return Poll::Ready(None);
+        }
+        shared.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for MessagePortStream {
+    fn drop(&mut self) {
+        // Detach the handlers before their closures are dropped
+        self.port.set_onmessage(None);
+        self.port.set_onmessageerror(None);
+        let _ = self
+            .port
+            .remove_event_listener_with_callback("close", self.on_close.as_ref().unchecked_ref());
+        self.port.close();
+    }
+}
@@ -1,32 +1,93 @@
 //! Bindings and conversions for
 //! [readable streams](https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream).
-use futures_util::io::AsyncRead;
-use futures_util::Stream;
-use js_sys::Object;
+use std::hash::{Hash, Hasher};
+
+use futures_util::io::{AllowStdIo, AsyncRead, AsyncSeek, AsyncWrite, AsyncWriteExt};
+use futures_util::stream::{iter, repeat, unfold, StreamExt, TryStreamExt};
+use futures_util::{future, Future, FutureExt, Sink, Stream};
+use js_sys::{Object, RangeError, Reflect, Symbol, TypeError, Uint8Array};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, AbortSignal, Blob, EventTarget, MessagePort};
 
+use bounded_tee::{bounded_tee, unzip};
 pub use byob_reader::ReadableStreamBYOBReader;
+use checksum::Checksum;
+pub use checksum::ChecksumHandle;
+use chunks_timeout::ChunksTimeout;
+use close_handle::Closable;
+pub use close_handle::CloseHandle;
+use close_on_idle::CloseOnIdle;
+use debounce::Debounce;
 pub use default_reader::ReadableStreamDefaultReader;
+use events::EventStream;
+pub use first_chunk_latency::FirstChunkLatency;
+use first_chunk_latency::Timed;
+use group_by::GroupBy;
+use into_async_iterator::IntoAsyncIterator;
 pub use into_async_read::IntoAsyncRead;
-pub use into_stream::IntoStream;
+pub use into_stream::{ChunkKind, IntoStream};
 use into_underlying_source::IntoUnderlyingSource;
+use length_prefixed::LengthPrefixedFrames;
+use message_port::MessagePortStream;
+use on_cleanup::OnCleanup;
+use on_done::OnDone;
+use pausable::Pausable;
+pub use pausable::PauseHandle;
+pub use pipe_handle::PipeHandle;
 pub use pipe_options::PipeOptions;
+use race::Race;
+use rate_limit::RateLimitBytes;
+use reconnecting::Reconnecting;
+use require_nonempty::RequireNonempty;
+pub use resume_token::ResumeToken;
+use resume_token::SeekableRead;
+use sample::Sample;
+pub use stream_error::StreamError;
+pub use stream_scope::StreamScope;
 
 use crate::queuing_strategy::QueuingStrategy;
 use crate::readable::into_underlying_byte_source::IntoUnderlyingByteSource;
-use crate::util::promise_to_void_future;
+use crate::util::{checked_cast_to_usize, js_to_io_error, promise_to_void_future};
 use crate::writable::WritableStream;
 
+mod bounded_tee;
 mod byob_reader;
+mod checksum;
+mod chunks_timeout;
+mod close_handle;
+mod close_on_idle;
+mod debounce;
 mod default_reader;
+mod events;
+mod first_chunk_latency;
+mod group_by;
+mod into_async_iterator;
 mod into_async_read;
 mod into_stream;
 mod into_underlying_byte_source;
 mod into_underlying_source;
+mod length_prefixed;
+mod message_port;
+mod on_cleanup;
+mod on_done;
+mod pausable;
+mod pipe_handle;
 mod pipe_options;
+mod race;
+mod rate_limit;
+mod reconnecting;
+mod require_nonempty;
+mod resume_token;
+mod sample;
+mod stream_error;
+mod stream_scope;
 pub mod sys;
 
+/// The `autoAllocateChunkSize` for byte streams created from other kinds of Rust streams.
+const DEFAULT_BYTE_STREAM_BUFFER_LEN: usize = 8 * 1024;
+
 /// A [`ReadableStream`](https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream).
 ///
 /// `ReadableStream`s can be created from a [raw JavaScript stream](sys::ReadableStream) with
@@ -44,13 +105,14 @@ pub mod sys;
 #[derive(Debug)]
 pub struct ReadableStream {
     raw: sys::ReadableStream,
+    label: Option<String>,
 }
 
 impl ReadableStream {
     /// Creates a new `ReadableStream` from a [JavaScript stream](sys::ReadableStream).
     #[inline]
     pub fn from_raw(raw: sys::ReadableStream) -> Self {
-        Self { raw }
+        Self { raw, label: None }
     }
 
     /// Creates a new `ReadableStream` from a [`Stream`].
@@ -67,16 +129,134 @@ impl ReadableStream {
     where
         St: Stream<Item = Result<JsValue, JsValue>> + 'static,
     {
-        let source = IntoUnderlyingSource::new(Box::new(stream));
         // Set HWM to 0 to prevent the JS ReadableStream from buffering chunks in its queue,
         // since the original Rust stream is better suited to handle that.
-        let strategy = QueuingStrategy::new(0.0);
+        Self::from_stream_with_high_water_mark(stream, 0.0)
+    }
+
+    /// Creates a new `ReadableStream` from a [`Stream`], which buffers up to `high_water_mark`
+    /// chunks in the readable stream's queue.
+    ///
+    /// Unlike [`from_stream`](Self::from_stream), the readable stream pulls chunks from the Rust
+    /// stream ahead of time, until its queue holds `high_water_mark` chunks. Chunks that
+    /// are ready right away are enqueued together in a single pull.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_stream_with_high_water_mark<St>(stream: St, high_water_mark: f64) -> Self
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'static,
+    {
+        let source = IntoUnderlyingSource::new(Box::new(stream), high_water_mark);
+        let strategy = QueuingStrategy::new(high_water_mark);
         let raw =
             sys::ReadableStreamExt::new_with_into_underlying_source(source, strategy.into_raw())
                 .unchecked_into();
         Self::from_raw(raw)
     }
 
+    /// Creates a new `ReadableStream` from a [`Stream`], by wrapping it in a JavaScript
+    /// [async iterable] and passing that to
+    /// [`ReadableStream.from()`](https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/from_static).
+    ///
+    /// This is an alternative to [`from_stream`](Self::from_stream), which some engines may be
+    /// able to optimize better. Canceling the readable stream drops the Rust stream.
+    /// If `ReadableStream.from()` is not supported, this falls back to `from_stream`.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [async iterable]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols#the_async_iterator_and_async_iterable_protocols
+    pub fn from_stream_as_async_iterable<St>(stream: St) -> Self
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'static,
+    {
+        if !supports_readable_stream_from() {
+            return Self::from_stream(stream);
+        }
+        let iterator = JsValue::from(IntoAsyncIterator::new(Box::new(stream)));
+        let iterable = Object::new();
+        // ReadableStream.from() calls this method exactly once, to get the iterator
+        let get_iterator = Closure::once_into_js(move || iterator);
+        Reflect::set(&iterable, &Symbol::async_iterator(), &get_iterator).unwrap_throw();
+        Self::from(iterable)
+    }
+
+    /// Creates a new `ReadableStream` from a [`Stream`], running `on_cleanup` once the
+    /// Rust stream is dropped.
+    ///
+    /// This behaves like [`from_stream`](Self::from_stream). The Rust stream is dropped
+    /// when the readable stream closes, errors or is
+    /// [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream),
+    /// so `on_cleanup` can release any external resources held by the producer.
+    /// It runs exactly once, unless the readable stream is never closed, errored or canceled.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_stream_with_cleanup<St, F>(stream: St, on_cleanup: F) -> Self
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'static,
+        F: FnOnce() + 'static,
+    {
+        Self::from_stream(OnCleanup::new(stream, on_cleanup))
+    }
+
+    /// Creates a new `ReadableStream` from a [`Stream`], along with a [`CloseHandle`]
+    /// to close the readable stream early.
+    ///
+    /// This behaves like [`from_stream`](Self::from_stream), except that the producer can
+    /// [close](CloseHandle::close) the readable stream before the Rust stream is exhausted.
+    /// Readers then see the end of the stream, as if the Rust stream had ended.
+    /// The Rust stream is dropped lazily, the next time the readable stream pulls from it
+    /// after it was closed.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_stream_closable<St>(stream: St) -> (Self, CloseHandle)
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'static,
+    {
+        let (stream, handle) = Closable::new(stream);
+        (Self::from_stream(stream), handle)
+    }
+
+    /// Creates a new `ReadableStream` whose chunks are produced by calling an async function.
+    ///
+    /// Every time the stream needs a new chunk, `f` is called and its future is awaited:
+    /// * If the future resolves with `Ok(Some(chunk))`, then `chunk` is enqueued.
+    /// * If the future resolves with `Ok(None)`, then the stream is closed.
+    /// * If the future resolves with `Err(error)`, then the stream is errored with `error`.
+    ///
+    /// This is a convenient alternative to [`from_stream`](Self::from_stream) for simple producers
+    /// that don't want to implement a [`Stream`].
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_pull_fn<F, Fut>(f: F) -> Self
+    where
+        F: FnMut() -> Fut + 'static,
+        Fut: Future<Output = Result<Option<JsValue>, JsValue>> + 'static,
+    {
+        Self::from_stream(unfold(f, |mut f| async move {
+            f().await.transpose().map(|result| (result, f))
+        }))
+    }
+
+    /// Creates a new `ReadableStream` from a [`Stream`] that may borrow data,
+    /// i.e. one that is not `'static`.
+    ///
+    /// Since the resulting JavaScript stream can outlive any Rust borrow, the given `stream`
+    /// is not moved into it. Instead, it is kept in the returned [`StreamScope`], which feeds
+    /// the stream's chunks to the `ReadableStream` while it is being polled. The scope must
+    /// therefore be polled concurrently with the consumer of the `ReadableStream`,
+    /// for example using [`join`](https://docs.rs/futures/0.3.30/futures/future/fn.join.html).
+    ///
+    /// If the scope is dropped before the Rust stream has ended,
+    /// then the `ReadableStream` becomes errored.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_stream_scoped<'a, St>(stream: St) -> (Self, StreamScope<'a>)
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'a,
+    {
+        let (scope, source) = StreamScope::new(stream);
+        (Self::from_stream(source), scope)
+    }
+
     /// Creates a new `ReadableStream` from an [`AsyncRead`].
     ///
     /// This creates a readable byte stream whose `autoAllocateChunkSize` is `default_buffer_len`.
@@ -100,6 +280,102 @@ impl ReadableStream {
         Self::from_raw(raw)
     }
 
+    /// Creates a new `ReadableStream` from a synchronous [`std::io::Read`].
+    ///
+    /// This behaves like [`from_async_read`](Self::from_async_read), with the reader wrapped in
+    /// an [`AllowStdIo`]. Every read on the stream calls [`read`](std::io::Read::read) directly,
+    /// and blocks until it returns.
+    ///
+    /// WebAssembly runs on a single thread, so a blocking read also blocks the JavaScript
+    /// event loop. This is fine for readers that never actually block, such as an in-memory
+    /// `&[u8]` or a [`Cursor`](std::io::Cursor). Do **not** use this for readers that wait for
+    /// external events, since those can never arrive while the read is blocking:
+    /// use an [`AsyncRead`] instead.
+    ///
+    /// **Panics** if readable byte streams are not supported by the browser.
+    ///
+    /// [`AllowStdIo`]: https://docs.rs/futures/0.3.30/futures/io/struct.AllowStdIo.html
+    /// [`AsyncRead`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncRead.html
+    pub fn from_sync_read<R>(read: R, default_buffer_len: usize) -> Self
+    where
+        R: std::io::Read + 'static,
+    {
+        Self::from_async_read(AllowStdIo::new(read), default_buffer_len)
+    }
+
+    /// Creates a new `ReadableStream` from a seekable [`AsyncRead`], starting at the given
+    /// `offset`, along with a [`ResumeToken`] to resume reading later on.
+    ///
+    /// This behaves like [`from_async_read`](Self::from_async_read), except that `async_read`
+    /// first [seeks][AsyncSeek::poll_seek] to `offset` before reading any bytes. The returned
+    /// token keeps track of how far the source has been read. If the stream is canceled,
+    /// a new stream can be created for the same data starting at the token's
+    /// [`offset`](ResumeToken::offset), so reading resumes where it left off.
+    ///
+    /// **Panics** if readable byte streams are not supported by the browser.
+    ///
+    /// [`AsyncRead`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncRead.html
+    /// [AsyncSeek::poll_seek]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncSeek.html#tymethod.poll_seek
+    pub fn from_async_read_seek<R>(
+        async_read: R,
+        default_buffer_len: usize,
+        offset: u64,
+    ) -> (Self, ResumeToken)
+    where
+        R: AsyncRead + AsyncSeek + 'static,
+    {
+        let (async_read, token) = SeekableRead::new(async_read, offset);
+        (Self::from_async_read(async_read, default_buffer_len), token)
+    }
+
+    /// Creates a new `ReadableStream` from an [`AsyncRead`], which can be aborted
+    /// with the given [`AbortSignal`](web_sys::AbortSignal).
+    ///
+    /// This behaves like [`from_async_read`](Self::from_async_read), except that when `signal`
+    /// is aborted, any pending read on `async_read` is canceled, `async_read` is dropped,
+    /// and the stream becomes errored with the signal's abort reason.
+    ///
+    /// **Panics** if readable byte streams are not supported by the browser.
+    ///
+    /// [`AsyncRead`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncRead.html
+    pub fn from_async_read_with_signal<R>(
+        async_read: R,
+        default_buffer_len: usize,
+        signal: AbortSignal,
+    ) -> Self
+    where
+        R: AsyncRead + 'static,
+    {
+        let source = IntoUnderlyingByteSource::new(Box::new(async_read), default_buffer_len)
+            .with_signal(signal);
+        let raw = sys::ReadableStreamExt::new_with_into_underlying_byte_source(source)
+            .expect_throw("readable byte streams not supported")
+            .unchecked_into();
+        Self::from_raw(raw)
+    }
+
+    /// Creates a new readable byte stream from a [`Stream`] of byte chunks.
+    ///
+    /// Unlike [`from_stream`](Self::from_stream), this creates a genuine
+    /// [readable byte stream](https://streams.spec.whatwg.org/#readable-byte-stream),
+    /// so it can be consumed using a [BYOB reader](ReadableStreamBYOBReader)
+    /// or [`into_async_read`](Self::into_async_read).
+    ///
+    /// This behaves like [`from_async_read`](Self::from_async_read) with a `default_buffer_len`
+    /// of 8 KiB. Errors from the stream are converted to a JavaScript `Error`
+    /// with the same message.
+    ///
+    /// **Panics** if readable byte streams are not supported by the browser.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn from_byte_stream<St>(stream: St) -> Self
+    where
+        St: Stream<Item = Result<Vec<u8>, JsValue>> + 'static,
+    {
+        let async_read = Box::pin(stream.map_err(js_to_io_error)).into_async_read();
+        Self::from_async_read(async_read, DEFAULT_BYTE_STREAM_BUFFER_LEN)
+    }
+
     /// Creates a new `ReadableStream` wrapping the provided [iterable] or [async iterable].
     ///
     /// This can be used to adapt various kinds of objects into a readable stream,
@@ -118,6 +394,33 @@ impl ReadableStream {
         Self::try_from(async_iterable).unwrap_throw()
     }
 
+    /// Creates a new `ReadableStream` that emits every message posted to the given
+    /// [`MessagePort`](https://developer.mozilla.org/en-US/docs/Web/API/MessagePort) as a chunk.
+    ///
+    /// This can be used to receive messages from a Web Worker through the streams API.
+    /// Messages are buffered from the moment this stream is created, so messages that arrive
+    /// before the first read are not lost.
+    ///
+    /// The stream closes when the port is closed from the other side, and errors if a message
+    /// cannot be deserialized. When the stream is canceled, the port is closed.
+    pub fn from_message_port(port: MessagePort) -> Self {
+        Self::from_stream(MessagePortStream::new(port))
+    }
+
+    /// Creates a new `ReadableStream` that emits every event of the given `event_type`
+    /// dispatched on the given [`EventTarget`](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget)
+    /// as a chunk.
+    ///
+    /// This can be used to consume DOM events, such as clicks or input changes, as a stream.
+    /// Events are buffered from the moment this stream is created, so events that are dispatched
+    /// before the first read are not lost.
+    ///
+    /// The stream never closes on its own. When the stream is canceled,
+    /// the event listener is removed from the target.
+    pub fn from_events(target: &EventTarget, event_type: &str) -> Self {
+        Self::from_stream(EventStream::new(target, event_type))
+    }
+
     /// Try to create a new `ReadableStream` wrapping the provided [iterable] or [async iterable].
     ///
     /// This can be used to adapt various kinds of objects into a readable stream,
@@ -137,6 +440,44 @@ impl ReadableStream {
         Ok(Self::from_raw(raw))
     }
 
+    /// Creates a new `ReadableStream` that errors with the given `reason`.
+    ///
+    /// The stream becomes errored as soon as it is first read from, so every read
+    /// returns `Err(reason)`. This can be useful for testing error handling.
+    pub fn error(reason: JsValue) -> Self {
+        Self::from_stream(iter(Some(Err(reason))))
+    }
+
+    /// Creates a new `ReadableStream` that emits the given `value` exactly `count` times,
+    /// and then closes.
+    ///
+    /// This can be useful as a placeholder, or for testing.
+    pub fn repeat(value: JsValue, count: usize) -> Self {
+        Self::from_stream(repeat(value).take(count).map(Ok))
+    }
+
+    /// Creates a new `ReadableStream` that cycles through the given `values`,
+    /// emitting `count` chunks in total before it closes.
+    ///
+    /// If `values` is empty, the stream closes immediately.
+    ///
+    /// This can be useful as a placeholder, or for testing.
+    pub fn cycle(values: Vec<JsValue>, count: usize) -> Self {
+        Self::from_stream(iter(values.into_iter().cycle().take(count)).map(Ok))
+    }
+
+    /// Sets a label for this `ReadableStream`, which is included in its [`Debug`] output.
+    ///
+    /// The label is also used as the name for an [`IntoAsyncRead`] created from this stream,
+    /// so it is prefixed to the message of any I/O error that it returns.
+    /// This can help to tell streams apart when debugging an application with multiple streams.
+    ///
+    /// [`Debug`]: std::fmt::Debug
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
     /// Acquires a reference to the underlying [JavaScript stream](sys::ReadableStream).
     #[inline]
     pub fn as_raw(&self) -> &sys::ReadableStream {
@@ -196,6 +537,23 @@ impl ReadableStream {
         ReadableStreamDefaultReader::new(self)
     }
 
+    /// Creates a [default reader](ReadableStreamDefaultReader), and runs the async function `f`
+    /// with it.
+    ///
+    /// The reader is moved into `f`, so it is dropped (and its lock
+    /// [released](https://streams.spec.whatwg.org/#release-a-lock)) as soon as the future
+    /// returned by `f` completes, regardless of whether it succeeds or fails.
+    /// Afterwards, the stream can be read again.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub async fn with_reader<'a, F, Fut, T>(&'a mut self, f: F) -> T
+    where
+        F: FnOnce(ReadableStreamDefaultReader<'a>) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        f(self.get_reader()).await
+    }
+
     /// Creates a [BYOB reader](ReadableStreamBYOBReader) and
     /// [locks](https://streams.spec.whatwg.org/#lock) the stream to the new reader.
     ///
@@ -263,6 +621,184 @@ impl ReadableStream {
         promise_to_void_future(promise).await
     }
 
+    /// [Pipes](https://streams.spec.whatwg.org/#piping) this readable stream to a given
+    /// writable stream in the background, returning a [`PipeHandle`] to wait for or abort the pipe.
+    ///
+    /// Both streams remain [locked](https://streams.spec.whatwg.org/#lock) for the duration
+    /// of the pipe. Errors and closures propagate as described in
+    /// [`pipe_to_with_options`](Self::pipe_to_with_options) with the default options.
+    ///
+    /// The pipe starts immediately, and keeps running even if the returned handle is dropped.
+    pub fn spawn_pipe_to(self, dest: WritableStream) -> PipeHandle {
+        let controller = AbortController::new().unwrap_throw();
+        let mut options = PipeOptions::new();
+        options.signal(controller.signal());
+        let promise = self
+            .as_raw()
+            .pipe_to_with_options(dest.as_raw(), &options.into_raw());
+        PipeHandle::new(JsFuture::from(promise), controller)
+    }
+
+    /// Reads all chunks from this readable stream and forwards them into the given [`Sink`].
+    ///
+    /// When the stream closes, the sink is closed as well. This is similar to
+    /// [`pipe_to`](Self::pipe_to), but avoids having to wrap the sink in a [`WritableStream`].
+    ///
+    /// This returns `()` if all chunks were forwarded and the sink closed successfully,
+    /// or `Err(error)` if either the stream or the sink encountered an `error`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub async fn drain_into<Si>(self, sink: Si) -> Result<(), JsValue>
+    where
+        Si: Sink<JsValue, Error = JsValue>,
+    {
+        self.into_stream().forward(sink).await
+    }
+
+    /// [Pipes](https://streams.spec.whatwg.org/#piping) this readable stream to all of the
+    /// given writable streams at the same time.
+    ///
+    /// Every chunk is written to each of the `dests`, and the next chunk is only read once all
+    /// of these writes have completed. This way, the slowest destination applies backpressure
+    /// to the whole pipe. Once this stream closes, all destinations are closed.
+    ///
+    /// This returns `()` if all chunks were written and all destinations closed successfully,
+    /// or `Err(error)` if this stream or any of the destinations encountered an `error`.
+    /// In that case, all destinations are [aborted](https://streams.spec.whatwg.org/#abort-a-writable-stream)
+    /// and this stream is [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream)
+    /// with that `error`.
+    ///
+    /// **Panics** if the stream is already locked to a reader,
+    /// or if any of the destinations is already locked to a writer.
+    pub async fn fanout(self, mut dests: Vec<WritableStream>) -> Result<(), JsValue> {
+        let mut stream = self.into_stream();
+        let mut writers = dests
+            .iter_mut()
+            .map(WritableStream::get_writer)
+            .collect::<Vec<_>>();
+        let result = async {
+            while let Some(chunk) = stream.try_next().await? {
+                future::try_join_all(writers.iter_mut().map(|writer| writer.write(chunk.clone())))
+                    .await?;
+            }
+            future::try_join_all(writers.iter_mut().map(|writer| writer.close())).await?;
+            Ok(())
+        }
+        .await;
+        if let Err(error) = &result {
+            // Ignore errors from aborting and canceling, report the original error instead
+            future::join_all(
+                writers
+                    .iter_mut()
+                    .map(|writer| writer.abort_with_reason(error)),
+            )
+            .await;
+            let _ = stream.cancel_with_reason(error).await;
+        }
+        result
+    }
+
+    /// Reads all [`Uint8Array`] chunks from this readable stream and writes their bytes
+    /// into the given [`AsyncWrite`], returning the total number of bytes copied.
+    ///
+    /// This is the equivalent of [`io::copy`] for readable streams. The writer is
+    /// [flushed](https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWriteExt.html#method.flush)
+    /// once the stream closes, but it is not closed.
+    ///
+    /// This returns an error if the stream encounters an `error` or produces a chunk that is not
+    /// a `Uint8Array`, or if writing fails. I/O errors are converted to a JavaScript `Error`
+    /// with the same message.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
+    /// [`io::copy`]: https://docs.rs/futures/0.3.30/futures/io/fn.copy.html
+    pub async fn copy_to_async_write<W>(self, writer: &mut W) -> Result<u64, JsValue>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let to_js_error = |err: std::io::Error| JsValue::from(js_sys::Error::new(&err.to_string()));
+        let mut chunks = Box::pin(self.into_byte_vec_stream());
+        let mut total = 0u64;
+        while let Some(chunk) = chunks.try_next().await? {
+            writer.write_all(&chunk).await.map_err(to_js_error)?;
+            total += chunk.len() as u64;
+        }
+        writer.flush().await.map_err(to_js_error)?;
+        Ok(total)
+    }
+
+    /// Reads all chunks from this readable stream and discards them.
+    ///
+    /// This can be used to consume a stream to completion, for example to release the resources
+    /// held by its underlying source, or when the other end requires the stream to be fully read.
+    /// Unlike [`cancel`](Self::cancel), this does not signal a loss of interest in the stream.
+    ///
+    /// This returns `()` once the stream closes, or `Err(error)` if the stream encountered an
+    /// `error`. If draining stops early (for example, because the returned future is dropped),
+    /// then the stream is canceled.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub async fn drain(self) -> Result<(), JsValue> {
+        self.into_stream()
+            .try_for_each(|_| future::ready(Ok(())))
+            .await
+    }
+
+    /// Reads all [`Uint8Array`] chunks from this readable stream and concatenates their bytes,
+    /// failing if the total size exceeds `max` bytes.
+    ///
+    /// This guards against memory exhaustion when buffering untrusted data, such as
+    /// a response body. As soon as a chunk would make the total exceed `max` bytes, the stream
+    /// is [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream) and this returns
+    /// a `RangeError`, which is also used as the cancellation reason.
+    ///
+    /// This also returns an error if the stream encounters an `error` or produces a chunk that
+    /// is not a `Uint8Array`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub async fn collect_bytes_capped(self, max: usize) -> Result<Vec<u8>, JsValue> {
+        let mut stream = self.into_stream();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = stream.try_next().await? {
+            let chunk = chunk
+                .dyn_into::<Uint8Array>()
+                .map_err(|_| TypeError::new("expected a Uint8Array chunk"))?;
+            let chunk_len = checked_cast_to_usize(chunk.byte_length());
+            if chunk_len > max - bytes.len() {
+                let error: JsValue =
+                    RangeError::new(&format!("stream exceeds the maximum size of {} bytes", max))
+                        .into();
+                // Ignore errors from canceling, report the size error instead
+                let _ = stream.cancel_with_reason(&error).await;
+                return Err(error);
+            }
+            let offset = bytes.len();
+            bytes.resize(offset + chunk_len, 0);
+            chunk.copy_to(&mut bytes[offset..]);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads all chunks from this readable stream into memory, and returns a new
+    /// `ReadableStream` that replays those chunks.
+    ///
+    /// This can be used when a stream must be inspected in full before it is forwarded,
+    /// or (together with [`tee`](Self::tee)) when it must be consumed multiple times.
+    /// Note that all chunks are kept in memory, so this should not be used on streams
+    /// of unbounded length.
+    ///
+    /// This returns the replaying stream once this stream closes, or `Err(error)` if
+    /// this stream encountered an `error`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub async fn buffer_all(self) -> Result<ReadableStream, JsValue> {
+        let chunks = self.into_stream().try_collect::<Vec<JsValue>>().await?;
+        Ok(Self::from_stream(iter(chunks).map(Ok)))
+    }
+
     /// [Tees](https://streams.spec.whatwg.org/#tee-a-readable-stream) this readable stream,
     /// returning the two resulting branches as new [`ReadableStream`] instances.
     ///
@@ -309,6 +845,614 @@ impl ReadableStream {
         ))
     }
 
+    /// [Tees](https://streams.spec.whatwg.org/#tee-a-readable-stream) this readable stream,
+    /// returning the two resulting branches, while limiting how far one branch can read ahead
+    /// of the other.
+    ///
+    /// Unlike [`tee`](Self::tee), which buffers chunks for a slow branch without bound,
+    /// this stops reading from the source while either branch has `max_lag` chunks queued
+    /// that it hasn't read yet. This way, a slow branch applies backpressure to the fast one.
+    /// If one branch is canceled, the other branch continues without limit.
+    /// The source is canceled once both branches are canceled.
+    ///
+    /// **Panics** if the stream is already locked to a reader, or if `max_lag` is zero.
+    pub fn tee_with_backpressure(self, max_lag: usize) -> (ReadableStream, ReadableStream) {
+        let (left, right) = bounded_tee(self.into_stream(), max_lag);
+        (Self::from_stream(left), Self::from_stream(right))
+    }
+
+    /// Partitions the chunks of this stream into sub-streams, grouped by the key returned
+    /// by `key` for each chunk.
+    ///
+    /// The returned [`Stream`] yields a new `(key, stream)` pair for every distinct key,
+    /// in the order in which the keys first appear. The sub-stream contains all chunks with
+    /// that key, in their original order. This can be used to route multiplexed data, such as
+    /// messages tagged with a channel ID, to a separate consumer for each channel.
+    ///
+    /// This stream is read whenever the returned `Stream` or any of the sub-streams needs a new
+    /// chunk. A chunk for another group is queued until that group's sub-stream reads it.
+    /// These queues are unbounded, so a slow group does not hold back the other groups, but also
+    /// does not apply any backpressure: the fastest reader determines how fast this stream is read.
+    /// Cancel any sub-stream that is no longer needed, so its chunks are dropped instead of queued.
+    ///
+    /// When this stream closes, all sub-streams close after reading their remaining chunks,
+    /// and the returned `Stream` ends. When this stream errors, all sub-streams error.
+    /// Chunks with a new key are dropped once the returned `Stream` is dropped.
+    /// This stream is canceled once the returned `Stream` and all sub-streams are dropped.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn group_by<K, F>(self, key: F) -> impl Stream<Item = (K, ReadableStream)>
+    where
+        K: Eq + Hash + Clone + 'static,
+        F: FnMut(&JsValue) -> K + 'static,
+    {
+        GroupBy::new(self.into_stream(), key)
+    }
+
+    /// Splits this stream of pairs into two streams, one with the first element of each pair
+    /// and one with the second element.
+    ///
+    /// Every chunk of this stream must be an `Array` with exactly 2 elements, otherwise both
+    /// returned streams error with a `TypeError`. The returned streams are kept in sync: neither
+    /// stream reads the next pair from this stream until the other stream has read the element
+    /// of the previous pair. Therefore, both streams should be read concurrently.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn unzip(self) -> (ReadableStream, ReadableStream) {
+        let (left, right) = unzip(self.into_stream());
+        (Self::from_stream(left), Self::from_stream(right))
+    }
+
+    /// Returns a new `ReadableStream` that reads from whichever of the two given streams
+    /// produces a chunk first, and then continues with only that stream.
+    ///
+    /// As soon as one stream yields a chunk (or an error), the other stream is canceled.
+    /// If one stream closes without producing any chunks, the other stream is used instead.
+    /// This can be useful to read from a primary and a fallback source.
+    ///
+    /// **Panics** if either stream is already locked to a reader.
+    pub fn race(a: ReadableStream, b: ReadableStream) -> ReadableStream {
+        Self::from_stream(Race::new(a.into_stream(), b.into_stream()))
+    }
+
+    /// Creates a new `ReadableStream` that reads from a stream created by `factory`,
+    /// and reconnects by calling `factory` again whenever that stream errors.
+    ///
+    /// This models an auto-reconnecting source, such as a server-sent event stream. After a
+    /// stream errors, the next stream is created after a delay of `backoff_ms` milliseconds,
+    /// which doubles for each consecutive failure. Once a stream successfully produces a chunk,
+    /// the delay is reset. If `max_retries` consecutive attempts fail, then the returned stream
+    /// errors with the last error. The returned stream closes when the current stream closes.
+    ///
+    /// Note that chunks are not deduplicated or replayed across reconnections:
+    /// each new stream should continue where the previous one left off.
+    pub fn reconnecting<F>(factory: F, max_retries: u32, backoff_ms: u32) -> ReadableStream
+    where
+        F: FnMut() -> ReadableStream + 'static,
+    {
+        Self::from_stream(Reconnecting::new(factory, max_retries, backoff_ms))
+    }
+
+    /// Returns a new `ReadableStream` that emits every `n`th chunk of this stream,
+    /// starting with the first one, and drops the others.
+    ///
+    /// Errors are always passed through. This can be used to sample a high-rate stream.
+    ///
+    /// **Panics** if the stream is already locked to a reader, or if `n` is zero.
+    pub fn step_by(self, n: usize) -> ReadableStream {
+        assert!(n > 0, "step must be greater than zero");
+        // Number of chunks to drop before the next one is kept
+        let mut skip = 0;
+        Self::from_stream(self.into_stream().try_filter(move |_| {
+            let keep = skip == 0;
+            skip = if keep { n - 1 } else { skip - 1 };
+            futures_util::future::ready(keep)
+        }))
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one, which errors with
+    /// `error` if this stream closes without emitting any chunk.
+    ///
+    /// This can be used for protocols that require at least one chunk, where an empty stream
+    /// is a failure. Errors from this stream are always passed through.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn require_nonempty(self, error: JsValue) -> ReadableStream {
+        Self::from_stream(RequireNonempty::new(self.into_stream(), error))
+    }
+
+    /// Returns a new `ReadableStream` that maps every chunk of this stream through the
+    /// async function `f`, running up to `concurrency` calls at the same time.
+    ///
+    /// The results are emitted in the same order as the original chunks, even if later
+    /// calls complete first. If a call fails, or if this stream errors, then the returned
+    /// stream errors as well. This is useful for chunks that each require asynchronous work,
+    /// such as a network request.
+    ///
+    /// **Panics** if the stream is already locked to a reader, or if `concurrency` is zero.
+    pub fn map_async<F, Fut>(self, concurrency: usize, mut f: F) -> ReadableStream
+    where
+        F: FnMut(JsValue) -> Fut + 'static,
+        Fut: Future<Output = Result<JsValue, JsValue>> + 'static,
+    {
+        assert!(concurrency > 0, "concurrency must be greater than zero");
+        Self::from_stream(
+            self.into_stream()
+                .map(move |result| match result {
+                    Ok(chunk) => future::Either::Left(f(chunk)),
+                    Err(err) => future::Either::Right(future::ready(Err(err))),
+                })
+                .buffered(concurrency),
+        )
+    }
+
+    /// Returns a new `ReadableStream` that drops every chunk whose key is equal to the key
+    /// of the chunk right before it.
+    ///
+    /// The key of each chunk is computed by `key`. Only consecutive duplicates are dropped,
+    /// so a chunk is emitted again if its key differs from that of the previous chunk.
+    /// This can be used to deduplicate repeated updates, for example by an id field.
+    /// Errors are always passed through.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn distinct_by<K, F>(self, mut key: F) -> ReadableStream
+    where
+        K: PartialEq + 'static,
+        F: FnMut(&JsValue) -> K + 'static,
+    {
+        let mut last_key = None;
+        Self::from_stream(self.into_stream().try_filter(move |chunk| {
+            let chunk_key = key(chunk);
+            let is_distinct = last_key.as_ref() != Some(&chunk_key);
+            last_key = Some(chunk_key);
+            future::ready(is_distinct)
+        }))
+    }
+
+    /// Returns a new `ReadableStream` that emits chunks of this stream for as long as
+    /// `predicate` returns `true`.
+    ///
+    /// At the first chunk for which `predicate` returns `false`, the returned stream closes
+    /// and this stream is [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream).
+    /// That chunk is dropped. Errors are always passed through.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn take_while<F>(self, mut predicate: F) -> ReadableStream
+    where
+        F: FnMut(&JsValue) -> bool + 'static,
+    {
+        Self::from_stream(
+            self.into_stream()
+                .try_take_while(move |chunk| future::ready(Ok(predicate(chunk)))),
+        )
+    }
+
+    /// Returns a new `ReadableStream` that drops chunks of this stream for as long as
+    /// `predicate` returns `true`, and then emits all remaining chunks.
+    ///
+    /// Once `predicate` has returned `false`, it is no longer called. Errors are always
+    /// passed through.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn skip_while<F>(self, mut predicate: F) -> ReadableStream
+    where
+        F: FnMut(&JsValue) -> bool + 'static,
+    {
+        Self::from_stream(
+            self.into_stream()
+                .try_skip_while(move |chunk| future::ready(Ok(predicate(chunk)))),
+        )
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one, but which holds back
+    /// all reads until the given `ready` future completes.
+    ///
+    /// This can be used to sequence a stream behind an initialization step. Until `ready`
+    /// completes, reads on the returned stream stay pending and no chunks are pulled
+    /// from this stream. Afterwards, all chunks are passed through as-is.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn gate<Fut>(self, ready: Fut) -> ReadableStream
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        let stream = self.into_stream();
+        Self::from_stream(ready.map(move |()| stream).flatten_stream())
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one, along with a
+    /// [`PauseHandle`] to pause and resume it.
+    ///
+    /// While paused, reads on the returned stream stay pending and no chunks are pulled
+    /// from this stream, so it experiences backpressure.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn pausable(self) -> (ReadableStream, PauseHandle) {
+        let (stream, handle) = Pausable::new(self.into_stream());
+        (Self::from_stream(stream), handle)
+    }
+
+    /// Returns a new `ReadableStream` that first emits the given `chunks`,
+    /// and then the chunks of this stream.
+    ///
+    /// This can be used to re-inject chunks that were already read from this stream,
+    /// such as a header that was peeked at.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn prepend(self, chunks: Vec<JsValue>) -> ReadableStream {
+        Self::from_stream(iter(chunks).map(Ok).chain(self.into_stream()))
+    }
+
+    /// Returns a new `ReadableStream` that concatenates the chunks of the inner streams
+    /// produced by this stream.
+    ///
+    /// Every chunk of this stream must be a [JavaScript stream](sys::ReadableStream).
+    /// Each inner stream is read completely, in order, before the next one is read from
+    /// this stream. If this stream produces a chunk that is not a `ReadableStream`,
+    /// or an inner stream is already locked, the returned stream errors with a `TypeError`.
+    /// If an inner stream errors, the returned stream errors with the same error.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn flatten(self) -> ReadableStream {
+        Self::from_stream(
+            self.into_stream()
+                .and_then(|chunk| {
+                    future::ready(
+                        chunk
+                            .dyn_into::<sys::ReadableStream>()
+                            .map_err(|_| TypeError::new("expected a ReadableStream chunk").into())
+                            .and_then(|raw| {
+                                Self::from_raw(raw)
+                                    .try_into_stream()
+                                    .map_err(|(err, _)| err.into())
+                            }),
+                    )
+                })
+                .try_flatten(),
+        )
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one,
+    /// calling `f` with a reference to each chunk as it passes through.
+    ///
+    /// This is intended for lightweight observation, such as collecting metrics.
+    /// The callback runs while the chunk is being read, so it must not block.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn tap<F>(self, f: F) -> ReadableStream
+    where
+        F: Fn(&JsValue) + 'static,
+    {
+        Self::from_stream(self.into_stream().inspect(move |result| {
+            if let Ok(chunk) = result {
+                f(chunk)
+            }
+        }))
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one,
+    /// calling `f` with the bytes of each [`Uint8Array`] chunk as it passes through.
+    ///
+    /// The bytes are copied before calling `f`, and the chunk itself is passed on unchanged.
+    /// This is useful for hex-dumping the traffic of a protocol stream while debugging.
+    /// Chunks that are not a `Uint8Array` are passed on without calling `f`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn tap_bytes<F>(self, f: F) -> ReadableStream
+    where
+        F: Fn(&[u8]) + 'static,
+    {
+        self.tap(move |chunk| {
+            if let Some(chunk) = chunk.dyn_ref::<Uint8Array>() {
+                f(&chunk.to_vec())
+            }
+        })
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one, along with a
+    /// [`ChecksumHandle`] to retrieve the checksum of its bytes.
+    ///
+    /// The bytes of each [`Uint8Array`] chunk are fed through the given `hasher` as they pass
+    /// through, and the final digest becomes available from the handle once the stream closes.
+    /// This can be used to verify downloaded content while streaming it.
+    /// Chunks that are not a `Uint8Array` are passed on without being hashed.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn with_checksum<H>(self, hasher: H) -> (ReadableStream, ChecksumHandle)
+    where
+        H: Hasher + 'static,
+    {
+        let (stream, handle) = Checksum::new(self.into_stream(), hasher);
+        (Self::from_stream(stream), handle)
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one,
+    /// calling `on_progress` with the fraction of `total` bytes read so far after each chunk.
+    ///
+    /// The fraction is computed from the cumulative length of all [`Uint8Array`] chunks,
+    /// and is clamped to at most `1.0` in case the stream turns out to be longer than `total`.
+    /// This can be used to drive a progress bar for a download with a known `Content-Length`.
+    /// Chunks that are not a `Uint8Array` are passed on without calling `on_progress`.
+    /// If `total` is zero, every chunk reports a progress of `1.0`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn with_progress<F>(self, total: u64, mut on_progress: F) -> ReadableStream
+    where
+        F: FnMut(f64) + 'static,
+    {
+        let mut received = 0u64;
+        Self::from_stream(self.into_stream().inspect(move |result| {
+            if let Some(chunk) = result.as_ref().ok().and_then(|c| c.dyn_ref::<Uint8Array>()) {
+                received += u64::from(chunk.byte_length());
+                let progress = if received >= total {
+                    1.0
+                } else {
+                    received as f64 / total as f64
+                };
+                on_progress(progress)
+            }
+        }))
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one,
+    /// calling `f` exactly once when the stream finishes.
+    ///
+    /// `f` receives `None` when the stream closes, or `Some(error)` when it errors.
+    /// This is useful for cleanup or for collecting metrics. If the returned stream is
+    /// canceled before this stream finishes, `f` is never called.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn on_done<F>(self, f: F) -> ReadableStream
+    where
+        F: FnOnce(Option<&JsValue>) + 'static,
+    {
+        Self::from_stream(OnDone::new(self.into_stream(), f))
+    }
+
+    /// Returns a new `ReadableStream` that groups the chunks of this stream into
+    /// JavaScript `Array`s of at most `max_count` chunks.
+    ///
+    /// A group is emitted as soon as it contains `max_count` chunks, or when `max_ms`
+    /// milliseconds have passed since its first chunk was read, whichever comes first.
+    /// Any remaining chunks are emitted as a final (smaller) group when this stream closes
+    /// or errors.
+    ///
+    /// **Panics** if the stream is already locked to a reader, or if `max_count` is zero.
+    pub fn chunks_timeout(self, max_count: usize, max_ms: u32) -> ReadableStream {
+        Self::from_stream(ChunksTimeout::new(self.into_stream(), max_count, max_ms))
+    }
+
+    /// Returns a new `ReadableStream` that only emits a chunk of this stream once no newer chunk
+    /// has arrived for `quiet_ms` milliseconds.
+    ///
+    /// During a burst of chunks, all but the last chunk are dropped. This can be used
+    /// to collapse a stream of rapid input events. When this stream closes or errors,
+    /// the last pending chunk is emitted right away.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn debounce(self, quiet_ms: u32) -> ReadableStream {
+        Self::from_stream(Debounce::new(self.into_stream(), quiet_ms))
+    }
+
+    /// Returns a new `ReadableStream` with the same chunks as this one, which closes
+    /// once no chunk has arrived for `idle_ms` milliseconds.
+    ///
+    /// When the idle period expires, this stream is
+    /// [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream) and the returned
+    /// stream closes gracefully, rather than erroring. The idle period restarts with every
+    /// chunk. This can be used for long-poll style endpoints that never close on their own.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn close_on_idle(self, idle_ms: u32) -> ReadableStream {
+        Self::from_stream(CloseOnIdle::new(self.into_stream(), idle_ms))
+    }
+
+    /// Returns a new `ReadableStream` that emits the most recent chunk of this stream
+    /// every `interval_ms` milliseconds.
+    ///
+    /// On every tick, the latest chunk that arrived since the previous tick is emitted,
+    /// and any chunks before it are dropped. If no chunk arrived, nothing is emitted for
+    /// that tick. Unlike [`debounce`](Self::debounce), the timing is driven by the ticks rather
+    /// than by the arrival of chunks. When this stream closes or errors, the last pending chunk
+    /// is emitted right away.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    pub fn sample(self, interval_ms: u32) -> ReadableStream {
+        Self::from_stream(Sample::new(self.into_stream(), interval_ms))
+    }
+
+    /// Returns a new `ReadableStream` with the same bytes as this one,
+    /// delivered at a rate of at most `bytes_per_sec` bytes per second.
+    ///
+    /// Every [`Uint8Array`] chunk is split into slices of at most a tenth of a second's worth
+    /// of bytes. After each slice, the returned stream waits for as long as it takes to deliver
+    /// that slice at the given rate, without pulling from this stream in the meantime.
+    /// This can be used to simulate a slow network, or to throttle a download.
+    /// Chunks that are not a `Uint8Array` are passed on without delay.
+    ///
+    /// **Panics** if `bytes_per_sec` is zero, or if the stream is already locked to a reader.
+    pub fn rate_limit_bytes(self, bytes_per_sec: u32) -> ReadableStream {
+        Self::from_stream(RateLimitBytes::new(self.into_stream(), bytes_per_sec))
+    }
+
+    /// Returns a new `ReadableStream` with the same bytes as this one, which prefetches
+    /// chunks from this stream while never buffering more than `max_bytes` in its queue.
+    ///
+    /// The returned stream measures its queue using a
+    /// [`ByteLengthQueuingStrategy`](https://developer.mozilla.org/en-US/docs/Web/API/ByteLengthQueuingStrategy)
+    /// with a high water mark of `max_bytes`. Once the queue is full, no more chunks are pulled
+    /// from this stream until the consumer has read enough of them, which applies backpressure
+    /// to a fast producer. A chunk that does not fit in the remaining budget is split, and its
+    /// remaining bytes are only enqueued once the consumer has made room for them.
+    /// Therefore, the chunk boundaries of the returned stream may differ from this stream.
+    ///
+    /// Every chunk must be a [`Uint8Array`]. Any other chunk errors the returned stream.
+    ///
+    /// **Panics** if `max_bytes` is zero, or if the stream is already locked to a reader.
+    pub fn with_memory_budget(self, max_bytes: usize) -> ReadableStream {
+        assert!(max_bytes > 0, "max_bytes must be greater than zero");
+        let high_water_mark = max_bytes as f64;
+        let source = IntoUnderlyingSource::new(Box::new(self.into_stream()), high_water_mark)
+            .with_chunk_splitting();
+        let strategy = QueuingStrategy::new_byte_length(high_water_mark);
+        let raw =
+            sys::ReadableStreamExt::new_with_into_underlying_source(source, strategy.into_raw())
+                .unchecked_into();
+        Self::from_raw(raw)
+    }
+
+    /// Converts this stream of [`Uint8Array`] chunks into a genuine
+    /// [readable byte stream](https://streams.spec.whatwg.org/#readable-byte-stream).
+    ///
+    /// This allows reading from a stream that was not created as a byte stream using a
+    /// [BYOB reader](ReadableStreamBYOBReader) or [`into_async_read`](Self::into_async_read).
+    /// The returned stream reads its bytes from this stream using a default reader.
+    ///
+    /// If this stream produces a chunk that is not a `Uint8Array`, the returned stream errors.
+    /// Errors from this stream are converted to a JavaScript `Error` with the same message.
+    ///
+    /// **Panics** if the stream is already locked to a reader,
+    /// or if readable byte streams are not supported by the browser.
+    pub fn into_byte_stream(self) -> ReadableStream {
+        Self::from_byte_stream(self.into_byte_vec_stream())
+    }
+
+    /// Converts this stream of [`Uint8Array`] chunks into a [`Stream`] of byte vectors.
+    ///
+    /// Each chunk is copied into a new `Vec<u8>`. If this stream produces a chunk that is not
+    /// a `Uint8Array`, the returned stream yields a `TypeError`.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn into_byte_vec_stream(self) -> impl Stream<Item = Result<Vec<u8>, JsValue>> {
+        self.into_stream().map(|result| {
+            result.and_then(|chunk| match chunk.dyn_into::<Uint8Array>() {
+                Ok(chunk) => Ok(chunk.to_vec()),
+                Err(_) => Err(TypeError::new("expected a Uint8Array chunk").into()),
+            })
+        })
+    }
+
+    /// Converts this stream of [`Uint8Array`] chunks into a [`Stream`] of length-prefixed frames.
+    ///
+    /// The bytes of this stream are parsed as a sequence of frames, each consisting of
+    /// a `prefix_len`-byte unsigned integer (in big-endian or little-endian byte order,
+    /// depending on `big_endian`) followed by that many bytes of payload. The returned stream
+    /// yields the payload of every frame, regardless of how the frames are split across chunks.
+    ///
+    /// If this stream ends in the middle of a frame, or produces a chunk that is not
+    /// a `Uint8Array`, the returned stream yields an error.
+    ///
+    /// **Panics** if `prefix_len` is not between 1 and 8, or if the stream is already locked
+    /// to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn into_length_prefixed_frames(
+        self,
+        prefix_len: usize,
+        big_endian: bool,
+    ) -> impl Stream<Item = Result<Vec<u8>, JsValue>> {
+        LengthPrefixedFrames::new(self.into_byte_vec_stream(), prefix_len, big_endian)
+    }
+
+    /// Converts this stream of [`Blob`] chunks into a [`Stream`] of byte vectors.
+    ///
+    /// The contents of each `Blob` are read with [`arrayBuffer()`] and copied into a new
+    /// `Vec<u8>`. If this stream produces a chunk that is not a `Blob`, or if reading a `Blob`
+    /// fails, the returned stream yields an error.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Blob`]: https://developer.mozilla.org/en-US/docs/Web/API/Blob
+    /// [`arrayBuffer()`]: https://developer.mozilla.org/en-US/docs/Web/API/Blob/arrayBuffer
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn into_blob_bytes_stream(self) -> impl Stream<Item = Result<Vec<u8>, JsValue>> {
+        self.into_stream().and_then(|chunk| async move {
+            let blob = chunk
+                .dyn_into::<Blob>()
+                .map_err(|_| TypeError::new("expected a Blob chunk"))?;
+            let buffer = JsFuture::from(blob.array_buffer()).await?;
+            Ok(Uint8Array::new(&buffer).to_vec())
+        })
+    }
+
+    /// Converts this `ReadableStream` into a [`Stream`], along with a [`FirstChunkLatency`]
+    /// handle to measure the time until its first chunk.
+    ///
+    /// The latency is measured with [`performance.now()`], from the moment this method is called
+    /// until the first chunk is read from the returned stream. This can be used to report
+    /// the time-to-first-byte of a response body to a performance dashboard.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [`performance.now()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/now
+    pub fn into_stream_timed(
+        self,
+    ) -> (
+        impl Stream<Item = Result<JsValue, JsValue>>,
+        FirstChunkLatency,
+    ) {
+        Timed::new(self.into_stream())
+    }
+
+    /// Converts this `ReadableStream` into a [`Stream`], transforming every chunk with `f`.
+    ///
+    /// This is a more convenient alternative to [`into_stream`](Self::into_stream) followed by
+    /// [`and_then`] for a synchronous transform. If `f` returns an error, the returned stream
+    /// yields that error in place of the chunk, and then continues with the next chunk.
+    /// Errors from this stream are yielded unchanged.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [`and_then`]: https://docs.rs/futures/0.3.30/futures/stream/trait.TryStreamExt.html#method.and_then
+    pub fn into_stream_try_map<T, F>(self, mut f: F) -> impl Stream<Item = Result<T, JsValue>>
+    where
+        F: FnMut(JsValue) -> Result<T, JsValue>,
+    {
+        self.into_stream()
+            .map(move |result| result.and_then(&mut f))
+    }
+
+    /// Converts this `ReadableStream` into a [`Stream`] whose errors are parsed into
+    /// a [`StreamError`].
+    ///
+    /// This behaves like [`into_stream`](Self::into_stream), except that every error is
+    /// inspected for known metadata. Currently, an error object with a numeric `retryAfter`
+    /// property is recognized, which is exposed as [`StreamError::retry_after_ms`].
+    /// This allows a consumer to back off before retrying, like with an HTTP `Retry-After`
+    /// header. The raw error is always kept in [`StreamError::value`].
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn into_stream_with_error_meta(self) -> impl Stream<Item = Result<JsValue, StreamError>> {
+        self.into_stream().map_err(StreamError::from)
+    }
+
+    /// Converts this `ReadableStream` into a [`Stream`] that yields each chunk
+    /// together with its size in bytes.
+    ///
+    /// The size of a [`Uint8Array`] chunk is its byte length. Any other chunk has size zero.
+    /// This allows tracking the throughput of a stream without having to measure each chunk
+    /// again.
+    ///
+    /// **Panics** if the stream is already locked to a reader.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    pub fn into_measured_stream(self) -> impl Stream<Item = Result<(JsValue, usize), JsValue>> {
+        self.into_stream().map_ok(|chunk| {
+            let size = chunk
+                .dyn_ref::<Uint8Array>()
+                .map_or(0, |chunk| checked_cast_to_usize(chunk.byte_length()));
+            (chunk, size)
+        })
+    }
+
     /// Converts this `ReadableStream` into a [`Stream`].
     ///
     /// Items and errors are represented by their raw [`JsValue`].
@@ -342,8 +1486,9 @@ impl ReadableStream {
     /// [`map_ok`]: https://docs.rs/futures/0.3.30/futures/stream/trait.TryStreamExt.html#method.map_ok
     /// [`map_err`]: https://docs.rs/futures/0.3.30/futures/stream/trait.TryStreamExt.html#method.map_err
     pub fn try_into_stream(mut self) -> Result<IntoStream<'static>, (js_sys::Error, Self)> {
+        let raw = self.as_raw().clone();
         let reader = ReadableStreamDefaultReader::new(&mut self).map_err(|err| (err, self))?;
-        Ok(IntoStream::new(reader, true))
+        Ok(IntoStream::new_owned(reader, raw))
     }
 
     /// Converts this `ReadableStream` into an [`AsyncRead`].
@@ -365,8 +1510,13 @@ impl ReadableStream {
     ///
     /// [`AsyncRead`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncRead.html
     pub fn try_into_async_read(mut self) -> Result<IntoAsyncRead<'static>, (js_sys::Error, Self)> {
+        let label = self.label.clone();
         let reader = ReadableStreamBYOBReader::new(&mut self).map_err(|err| (err, self))?;
-        Ok(IntoAsyncRead::new(reader, true))
+        let async_read = IntoAsyncRead::new(reader, true);
+        Ok(match label {
+            Some(label) => async_read.with_name(&label),
+            None => async_read,
+        })
     }
 }
 
@@ -380,3 +1530,9 @@ where
         Self::from_stream(stream)
     }
 }
+
+fn supports_readable_stream_from() -> bool {
+    Reflect::get(&js_sys::global(), &"ReadableStream".into())
+        .and_then(|constructor| Reflect::has(&constructor, &"from".into()))
+        .unwrap_or_default()
+}
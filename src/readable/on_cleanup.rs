@@ -0,0 +1,55 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+/// A [`Stream`] for the [`from_stream_with_cleanup`](super::ReadableStream::from_stream_with_cleanup)
+/// method.
+///
+/// The cleanup function runs when this stream is dropped.
+pub(super) struct OnCleanup<St, F>
+where
+    F: FnOnce(),
+{
+    stream: Pin<Box<St>>,
+    on_cleanup: Option<F>,
+}
+
+impl<St, F> OnCleanup<St, F>
+where
+    F: FnOnce(),
+{
+    pub fn new(stream: St, on_cleanup: F) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            on_cleanup: Some(on_cleanup),
+        }
+    }
+}
+
+impl<St, F> Unpin for OnCleanup<St, F> where F: FnOnce() {}
+
+impl<St, F> Stream for OnCleanup<St, F>
+where
+    St: Stream<Item = Result<JsValue, JsValue>>,
+    F: FnOnce(),
+{
+    type Item = Result<JsValue, JsValue>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+impl<St, F> Drop for OnCleanup<St, F>
+where
+    F: FnOnce(),
+{
+    fn drop(&mut self) {
+        if let Some(on_cleanup) = self.on_cleanup.take() {
+            on_cleanup();
+        }
+    }
+}
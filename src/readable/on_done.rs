@@ -0,0 +1,47 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`on_done`](super::ReadableStream::on_done) method.
+pub(super) struct OnDone<F> {
+    stream: IntoStream<'static>,
+    f: Option<F>,
+}
+
+impl<F> OnDone<F>
+where
+    F: FnOnce(Option<&JsValue>),
+{
+    pub fn new(stream: IntoStream<'static>, f: F) -> Self {
+        Self { stream, f: Some(f) }
+    }
+
+    fn done(&mut self, error: Option<&JsValue>) {
+        if let Some(f) = self.f.take() {
+            f(error);
+        }
+    }
+}
+
+impl<F> Unpin for OnDone<F> {}
+
+impl<F> Stream for OnDone<F>
+where
+    F: FnOnce(Option<&JsValue>),
+{
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = futures_util::ready!(self.stream.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(_)) => {}
+            Some(Err(error)) => self.done(Some(error)),
+            None => self.done(None),
+        }
+        Poll::Ready(item)
+    }
+}
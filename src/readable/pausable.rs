@@ -0,0 +1,79 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use super::IntoStream;
+
+#[derive(Debug, Default)]
+struct PauseState {
+    paused: bool,
+    waker: Option<Waker>,
+}
+
+/// A handle to pause and resume a stream,
+/// as returned by [`pausable`](super::ReadableStream::pausable).
+///
+/// The stream keeps running normally if this handle is dropped while the stream is not paused.
+#[derive(Debug, Clone)]
+pub struct PauseHandle {
+    state: Rc<RefCell<PauseState>>,
+}
+
+impl PauseHandle {
+    /// Pauses the stream.
+    ///
+    /// Reads that are not yet fulfilled stay pending until the stream is [resumed](Self::resume),
+    /// and no more chunks are pulled from the source in the meantime.
+    pub fn pause(&self) {
+        self.state.borrow_mut().paused = true;
+    }
+
+    /// Resumes the stream after it was [paused](Self::pause).
+    pub fn resume(&self) {
+        let mut state = self.state.borrow_mut();
+        state.paused = false;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if the stream is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.state.borrow().paused
+    }
+}
+
+/// A [`Stream`] for the [`pausable`](super::ReadableStream::pausable) method.
+pub(super) struct Pausable {
+    stream: IntoStream<'static>,
+    state: Rc<RefCell<PauseState>>,
+}
+
+impl Pausable {
+    pub fn new(stream: IntoStream<'static>) -> (Self, PauseHandle) {
+        let state = Rc::new(RefCell::new(PauseState::default()));
+        let handle = PauseHandle {
+            state: state.clone(),
+        };
+        (Self { stream, state }, handle)
+    }
+}
+
+impl Stream for Pausable {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.paused {
+                state.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+        self.stream.poll_next_unpin(cx)
+    }
+}
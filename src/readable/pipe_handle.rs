@@ -0,0 +1,40 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::AbortController;
+
+/// A handle to a pipe running in the background,
+/// as returned by [`spawn_pipe_to`](super::ReadableStream::spawn_pipe_to).
+///
+/// The pipe keeps running even if this handle is dropped.
+#[must_use = "dropping a pipe handle does not stop the pipe, use `abort()` for that"]
+#[derive(Debug)]
+pub struct PipeHandle {
+    fut: JsFuture,
+    controller: AbortController,
+}
+
+impl PipeHandle {
+    #[inline]
+    pub(super) fn new(fut: JsFuture, controller: AbortController) -> Self {
+        Self { fut, controller }
+    }
+
+    /// Waits for the pipe to finish.
+    ///
+    /// This returns `()` if the pipe completes successfully, or `Err(error)` if any `error`
+    /// was encountered during the process. If the pipe was [aborted](Self::abort), this returns
+    /// an `AbortError`.
+    pub async fn join(self) -> Result<(), JsValue> {
+        let js_value = self.fut.await?;
+        debug_assert!(js_value.is_undefined());
+        Ok(())
+    }
+
+    /// Aborts the pipe.
+    ///
+    /// The source readable stream will be [canceled](https://streams.spec.whatwg.org/#cancel-a-readable-stream)
+    /// and the destination writable stream will be [aborted](https://streams.spec.whatwg.org/#abort-a-writable-stream).
+    pub fn abort(&self) {
+        self.controller.abort()
+    }
+}
@@ -0,0 +1,69 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::ready;
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`race`](super::ReadableStream::race) method.
+///
+/// Polls both streams until one of them yields an item, then drops (and thereby cancels)
+/// the other one.
+pub(super) struct Race {
+    a: Option<IntoStream<'static>>,
+    b: Option<IntoStream<'static>>,
+}
+
+impl Race {
+    pub fn new(a: IntoStream<'static>, b: IntoStream<'static>) -> Self {
+        Self {
+            a: Some(a),
+            b: Some(b),
+        }
+    }
+}
+
+impl Stream for Race {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match (&mut this.a, &mut this.b) {
+                (Some(a), Some(b)) => {
+                    if let Poll::Ready(item) = a.poll_next_unpin(cx) {
+                        match item {
+                            Some(item) => {
+                                // First stream won, cancel the second one
+                                this.b = None;
+                                return Poll::Ready(Some(item));
+                            }
+                            None => {
+                                // First stream ended without a chunk, continue with the second one
+                                this.a = None;
+                                continue;
+                            }
+                        }
+                    }
+                    match ready!(b.poll_next_unpin(cx)) {
+                        Some(item) => {
+                            // Second stream won, cancel the first one
+                            this.a = None;
+                            return Poll::Ready(Some(item));
+                        }
+                        None => {
+                            // Second stream ended without a chunk, continue with the first one
+                            this.b = None;
+                        }
+                    }
+                }
+                (Some(winner), None) | (None, Some(winner)) => {
+                    return winner.poll_next_unpin(cx);
+                }
+                (None, None) => return Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::ready;
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::timer::{sleep, Sleep};
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`rate_limit_bytes`](super::ReadableStream::rate_limit_bytes) method.
+///
+/// Splits every [`Uint8Array`] chunk into slices of at most a tenth of a second's worth
+/// of bytes, and waits after each slice for as long as it takes to deliver it at the given rate.
+pub(super) struct RateLimitBytes {
+    stream: IntoStream<'static>,
+    bytes_per_sec: u32,
+    slice_len: u32,
+    // The remaining bytes of the current chunk
+    pending: Option<Uint8Array>,
+    timer: Option<Sleep>,
+}
+
+impl RateLimitBytes {
+    pub fn new(stream: IntoStream<'static>, bytes_per_sec: u32) -> Self {
+        assert!(bytes_per_sec > 0, "bytes_per_sec must be greater than zero");
+        Self {
+            stream,
+            bytes_per_sec,
+            slice_len: (bytes_per_sec / 10).max(1),
+            pending: None,
+            timer: None,
+        }
+    }
+}
+
+impl Stream for RateLimitBytes {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        // Wait until the previous slice has been paid for
+        if let Some(timer) = this.timer.as_mut() {
+            ready!(timer.poll_unpin(cx));
+            this.timer = None;
+        }
+        let chunk = match this.pending.take() {
+            Some(chunk) => chunk,
+            None => match ready!(this.stream.poll_next_unpin(cx)) {
+                Some(Ok(chunk)) => match chunk.dyn_into::<Uint8Array>() {
+                    Ok(chunk) => chunk,
+                    // Not a byte chunk, pass it through as-is
+                    Err(chunk) => return Poll::Ready(Some(Ok(chunk))),
+                },
+                item => return Poll::Ready(item),
+            },
+        };
+        let len = chunk.byte_length();
+        let slice = if len > this.slice_len {
+            this.pending = Some(chunk.subarray(this.slice_len, len));
+            chunk.subarray(0, this.slice_len)
+        } else {
+            chunk
+        };
+        let slice_len = u64::from(slice.byte_length());
+        let bytes_per_sec = u64::from(this.bytes_per_sec);
+        let delay_ms = (slice_len * 1000).div_ceil(bytes_per_sec);
+        this.timer = Some(sleep(u32::try_from(delay_ms).unwrap_or(u32::MAX)));
+        Poll::Ready(Some(Ok(slice.into())))
+    }
+}
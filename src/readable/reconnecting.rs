@@ -0,0 +1,83 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use wasm_bindgen::prelude::*;
+
+use crate::timer::{sleep, Sleep};
+
+use super::{IntoStream, ReadableStream};
+
+/// A [`Stream`] for the [`reconnecting`](super::ReadableStream::reconnecting) method.
+///
+/// Reads from a stream created by the factory, and creates a new one whenever
+/// the current stream errors, waiting longer after each consecutive failure.
+pub(super) struct Reconnecting<F> {
+    factory: F,
+    current: Option<IntoStream<'static>>,
+    backoff: Option<Sleep>,
+    retries: u32,
+    max_retries: u32,
+    backoff_ms: u32,
+}
+
+impl<F> Reconnecting<F>
+where
+    F: FnMut() -> ReadableStream,
+{
+    pub fn new(factory: F, max_retries: u32, backoff_ms: u32) -> Self {
+        Self {
+            factory,
+            current: None,
+            backoff: None,
+            retries: 0,
+            max_retries,
+            backoff_ms,
+        }
+    }
+}
+
+impl<F> Unpin for Reconnecting<F> {}
+
+impl<F> Stream for Reconnecting<F>
+where
+    F: FnMut() -> ReadableStream,
+{
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(backoff) = this.backoff.as_mut() {
+                futures_util::ready!(backoff.poll_unpin(cx));
+                this.backoff = None;
+            }
+            let factory = &mut this.factory;
+            let current = this.current.get_or_insert_with(|| factory().into_stream());
+            match futures_util::ready!(current.poll_next_unpin(cx)) {
+                Some(Ok(chunk)) => {
+                    // Connected successfully, start counting failures from scratch
+                    this.retries = 0;
+                    return Poll::Ready(Some(Ok(chunk)));
+                }
+                Some(Err(error)) => {
+                    this.current = None;
+                    if this.retries >= this.max_retries {
+                        return Poll::Ready(Some(Err(error)));
+                    }
+                    // Double the delay after each consecutive failure
+                    let delay = this
+                        .backoff_ms
+                        .saturating_mul(1u32.checked_shl(this.retries).unwrap_or(u32::MAX));
+                    this.retries += 1;
+                    this.backoff = Some(sleep(delay));
+                }
+                None => {
+                    this.current = None;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
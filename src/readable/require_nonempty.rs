@@ -0,0 +1,38 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`require_nonempty`](super::ReadableStream::require_nonempty) method.
+pub(super) struct RequireNonempty {
+    stream: IntoStream<'static>,
+    // The error to emit if the stream ends, until the first chunk is seen
+    error: Option<JsValue>,
+}
+
+impl RequireNonempty {
+    pub fn new(stream: IntoStream<'static>, error: JsValue) -> Self {
+        Self {
+            stream,
+            error: Some(error),
+        }
+    }
+}
+
+impl Stream for RequireNonempty {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = futures_util::ready!(self.stream.poll_next_unpin(cx));
+        Poll::Ready(match item {
+            Some(item) => {
+                self.error = None;
+                Some(item)
+            }
+            None => self.error.take().map(Err),
+        })
+    }
+}
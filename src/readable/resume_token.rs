@@ -0,0 +1,73 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::Cell;
+use std::io::SeekFrom;
+use std::rc::Rc;
+
+use futures_util::io::{AsyncRead, AsyncSeek};
+use futures_util::ready;
+
+/// A token to resume reading a seekable byte source,
+/// as returned by [`from_async_read_seek`](super::ReadableStream::from_async_read_seek).
+///
+/// The token keeps track of the offset up to which bytes have been read from the source.
+/// After the stream is canceled (or closes or errors), a new stream can be created with
+/// [`from_async_read_seek`](super::ReadableStream::from_async_read_seek) starting at
+/// [`offset`](Self::offset), to continue where the previous stream left off.
+#[derive(Debug, Clone)]
+pub struct ResumeToken {
+    offset: Rc<Cell<u64>>,
+}
+
+impl ResumeToken {
+    /// Returns the offset in the source right after the last byte that was read.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset.get()
+    }
+}
+
+/// An [`AsyncRead`] for the [`from_async_read_seek`](super::ReadableStream::from_async_read_seek)
+/// method.
+///
+/// It seeks to the start offset before the first read, and records the offset after every read.
+pub(super) struct SeekableRead<R> {
+    inner: Pin<Box<R>>,
+    start: Option<u64>,
+    offset: Rc<Cell<u64>>,
+}
+
+impl<R> SeekableRead<R> {
+    pub fn new(inner: R, start: u64) -> (Self, ResumeToken) {
+        let offset = Rc::new(Cell::new(start));
+        let token = ResumeToken {
+            offset: offset.clone(),
+        };
+        let read = SeekableRead {
+            inner: Box::pin(inner),
+            start: Some(start),
+            offset,
+        };
+        (read, token)
+    }
+}
+
+impl<R> AsyncRead for SeekableRead<R>
+where
+    R: AsyncRead + AsyncSeek,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if let Some(start) = self.start {
+            // Seek to the start offset before reading any bytes
+            ready!(self.inner.as_mut().poll_seek(cx, SeekFrom::Start(start)))?;
+            self.start = None;
+        }
+        let bytes_read = ready!(self.inner.as_mut().poll_read(cx, buf))?;
+        self.offset.set(self.offset.get() + bytes_read as u64);
+        Poll::Ready(Ok(bytes_read))
+    }
+}
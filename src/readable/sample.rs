@@ -0,0 +1,80 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::FutureExt;
+use wasm_bindgen::prelude::*;
+
+use crate::timer::{sleep, Sleep};
+
+use super::IntoStream;
+
+/// A [`Stream`] for the [`sample`](super::ReadableStream::sample) method.
+///
+/// Keeps only the latest chunk from the wrapped stream, and emits it on every tick
+/// of a timer that fires every `interval_ms` milliseconds.
+pub(super) struct Sample {
+    stream: Option<IntoStream<'static>>,
+    latest: Option<JsValue>,
+    timer: Option<Sleep>,
+    error: Option<JsValue>,
+    interval_ms: u32,
+}
+
+impl Sample {
+    pub fn new(stream: IntoStream<'static>, interval_ms: u32) -> Self {
+        Self {
+            stream: Some(stream),
+            latest: None,
+            timer: None,
+            error: None,
+            interval_ms,
+        }
+    }
+}
+
+impl Stream for Sample {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            let stream = match this.stream.as_mut() {
+                Some(stream) => stream,
+                None => {
+                    // Source has ended, emit the latest chunk right away before ending or erroring
+                    if let Some(chunk) = this.latest.take() {
+                        this.timer = None;
+                        return Poll::Ready(Some(Ok(chunk)));
+                    }
+                    return Poll::Ready(this.error.take().map(Err));
+                }
+            };
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    // Replace the previous chunk, but keep the timer running
+                    this.latest = Some(chunk);
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.error = Some(err);
+                    this.stream = None;
+                }
+                Poll::Ready(None) => {
+                    this.stream = None;
+                }
+                Poll::Pending => {
+                    let interval_ms = this.interval_ms;
+                    let timer = this.timer.get_or_insert_with(|| sleep(interval_ms));
+                    while timer.poll_unpin(cx).is_ready() {
+                        // Start the next tick, and emit the latest chunk (if any)
+                        *timer = sleep(interval_ms);
+                        if let Some(chunk) = this.latest.take() {
+                            return Poll::Ready(Some(Ok(chunk)));
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use js_sys::Reflect;
+use wasm_bindgen::prelude::*;
+
+/// An error from a stream, along with any metadata that could be parsed from it,
+/// as returned by [`into_stream_with_error_meta`](super::ReadableStream::into_stream_with_error_meta).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamError {
+    /// The raw error value.
+    pub value: JsValue,
+    /// How many milliseconds to wait before retrying, if the error specified it.
+    ///
+    /// This is taken from the `retryAfter` property of an error object,
+    /// if that property holds a non-negative number of milliseconds.
+    pub retry_after_ms: Option<u32>,
+}
+
+impl From<JsValue> for StreamError {
+    fn from(value: JsValue) -> Self {
+        let retry_after_ms = if value.is_object() {
+            Reflect::get(&value, &JsValue::from_str("retryAfter"))
+                .ok()
+                .and_then(|retry_after| retry_after.as_f64())
+                .filter(|retry_after| *retry_after >= 0.0)
+                // Saturates at u32::MAX for very large values
+                .map(|retry_after| retry_after as u32)
+        } else {
+            None
+        };
+        Self {
+            value,
+            retry_after_ms,
+        }
+    }
+}
+
+impl From<StreamError> for JsValue {
+    #[inline]
+    fn from(error: StreamError) -> Self {
+        error.value
+    }
+}
@@ -0,0 +1,162 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::stream::{Stream, StreamExt};
+use wasm_bindgen::prelude::*;
+
+type JsValueStream<'a> = dyn Stream<Item = Result<JsValue, JsValue>> + 'a;
+
+/// A scope for the [`from_stream_scoped`](super::ReadableStream::from_stream_scoped) method.
+///
+/// This future owns the borrowed Rust stream, and feeds its chunks to the corresponding
+/// [`ReadableStream`](super::ReadableStream) whenever that stream is read from.
+/// It must be polled concurrently with the readable stream's consumer, for example with
+/// [`join`](https://docs.rs/futures/0.3.30/futures/future/fn.join.html).
+///
+/// The scope completes when the Rust stream ends, or when the readable stream is canceled.
+/// If the scope is dropped before then, the readable stream becomes errored.
+#[must_use = "futures do nothing unless polled"]
+pub struct StreamScope<'a> {
+    stream: Option<Pin<Box<JsValueStream<'a>>>>,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl<'a> StreamScope<'a> {
+    pub(super) fn new<St>(stream: St) -> (Self, ScopedSource)
+    where
+        St: Stream<Item = Result<JsValue, JsValue>> + 'a,
+    {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let scope = StreamScope {
+            stream: Some(Box::pin(stream)),
+            shared: shared.clone(),
+        };
+        (scope, ScopedSource { shared })
+    }
+}
+
+impl core::fmt::Debug for StreamScope<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamScope").finish_non_exhaustive()
+    }
+}
+
+impl Future for StreamScope<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let stream = match this.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(()),
+        };
+        let mut shared = this.shared.borrow_mut();
+        if shared.canceled {
+            // Readable stream was canceled, drop the Rust stream
+            drop(shared);
+            this.stream = None;
+            return Poll::Ready(());
+        }
+        if !shared.wants_chunk {
+            // Wait for the next read
+            shared.scope_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let item = match stream.poll_next_unpin(cx) {
+            Poll::Ready(item) => item,
+            Poll::Pending => return Poll::Pending,
+        };
+        shared.wants_chunk = false;
+        let done = match item {
+            Some(Ok(chunk)) => {
+                shared.slot = Some(Ok(chunk));
+                false
+            }
+            Some(Err(err)) => {
+                shared.slot = Some(Err(err));
+                true
+            }
+            None => true,
+        };
+        shared.done = done;
+        if let Some(waker) = shared.source_waker.take() {
+            waker.wake();
+        }
+        if done {
+            drop(shared);
+            this.stream = None;
+            Poll::Ready(())
+        } else {
+            // Wait for the next read
+            shared.scope_waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for StreamScope<'_> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        if !shared.done {
+            shared.dropped = true;
+        }
+        if let Some(waker) = shared.source_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    slot: Option<Result<JsValue, JsValue>>,
+    wants_chunk: bool,
+    done: bool,
+    dropped: bool,
+    canceled: bool,
+    scope_waker: Option<Waker>,
+    source_waker: Option<Waker>,
+}
+
+/// The `'static` stream that is wrapped in the readable stream,
+/// receiving chunks from a [`StreamScope`].
+pub(super) struct ScopedSource {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Stream for ScopedSource {
+    type Item = Result<JsValue, JsValue>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+        if let Some(item) = shared.slot.take() {
+            return Poll::Ready(Some(item));
+        }
+        if shared.done {
+            return Poll::Ready(None);
+        }
+        if shared.dropped {
+            shared.done = true;
+            let error = js_sys::Error::new("stream scope was dropped");
+            return Poll::Ready(Some(Err(error.into())));
+        }
+        shared.wants_chunk = true;
+        shared.source_waker = Some(cx.waker().clone());
+        if let Some(waker) = shared.scope_waker.take() {
+            waker.wake();
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for ScopedSource {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.canceled = true;
+        if let Some(waker) = shared.scope_waker.take() {
+            waker.wake();
+        }
+    }
+}
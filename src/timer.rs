@@ -0,0 +1,72 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::FutureExt;
+use js_sys::{Function, Promise};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, timeout: i32) -> JsValue;
+
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(handle: &JsValue);
+
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// A future that completes after a given number of milliseconds,
+/// using the global `setTimeout()` function.
+///
+/// The timer is cleared when the future is dropped.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub(crate) struct Sleep {
+    fut: JsFuture,
+    handle: JsValue,
+}
+
+impl Sleep {
+    pub fn new(ms: u32) -> Self {
+        let timeout = i32::try_from(ms).unwrap_or(i32::MAX);
+        let mut handle = JsValue::undefined();
+        // The executor runs synchronously, so the handle is set before the promise is returned.
+        let promise = Promise::new(&mut |resolve, _reject| {
+            handle = set_timeout(&resolve, timeout);
+        });
+        Self {
+            fut: JsFuture::from(promise),
+            handle,
+        }
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.fut.poll_unpin(cx).map(|_| ())
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        clear_timeout(&self.handle);
+    }
+}
+
+#[inline]
+pub(crate) fn sleep(ms: u32) -> Sleep {
+    Sleep::new(ms)
+}
+
+/// Returns the current high resolution time in milliseconds,
+/// using the global `performance.now()` function.
+#[inline]
+pub(crate) fn now() -> f64 {
+    performance_now()
+}
@@ -1,9 +1,16 @@
 //! Bindings and conversions for
 //! [transform streams](https://developer.mozilla.org/en-US/docs/Web/API/TransformStream).
-use crate::readable::ReadableStream;
-use crate::writable::WritableStream;
+use js_sys::Object;
+use wasm_bindgen::prelude::*;
+use web_sys::QueuingStrategy;
+
+pub use typed::TypedTransformStream;
+
+use crate::readable::{IntoStream, ReadableStream};
+use crate::writable::{IntoSink, WritableStream};
 
 pub mod sys;
+mod typed;
 
 /// A [`TransformStream`](https://developer.mozilla.org/en-US/docs/Web/API/TransformStream).
 ///
@@ -30,6 +37,31 @@ impl TransformStream {
         Self { raw }
     }
 
+    /// Creates a new `TransformStream` from a JavaScript
+    /// [transformer](https://developer.mozilla.org/en-US/docs/Web/API/TransformStream/TransformStream#transformer)
+    /// object, with custom queuing strategies for its writable and readable side.
+    ///
+    /// The `writable_strategy` controls how many chunks can be queued on the writable side
+    /// before writes apply backpressure, and the `readable_strategy` controls how many
+    /// transformed chunks are buffered on the readable side before the transformer stops
+    /// being called. An empty `transformer` object creates an identity transform.
+    ///
+    /// This returns an error if the `TransformStream` constructor throws, for example
+    /// when a strategy has an invalid high water mark.
+    pub fn new_with_strategies(
+        transformer: &Object,
+        writable_strategy: &QueuingStrategy,
+        readable_strategy: &QueuingStrategy,
+    ) -> Result<Self, JsValue> {
+        let raw =
+            sys::TransformStream::new_with_transformer_and_writable_strategy_and_readable_strategy(
+                transformer,
+                writable_strategy,
+                readable_strategy,
+            )?;
+        Ok(Self::from_raw(raw))
+    }
+
     /// Acquires a reference to the underlying [JavaScript stream](sys::TransformStream).
     #[inline]
     pub fn as_raw(&self) -> &sys::TransformStream {
@@ -43,14 +75,49 @@ impl TransformStream {
     }
 
     /// Returns the readable side of the transform stream.
+    ///
+    /// Every call returns a new wrapper around the same JavaScript stream. Locking one wrapper
+    /// (for example, by acquiring a reader) also locks any other wrapper for the same side.
+    /// Use [`into_readable_writable`](Self::into_readable_writable) to obtain each side
+    /// exactly once.
     #[inline]
     pub fn readable(&self) -> ReadableStream {
         ReadableStream::from_raw(self.as_raw().readable())
     }
 
     /// Returns the writable side of the transform stream.
+    ///
+    /// Every call returns a new wrapper around the same JavaScript stream. Locking one wrapper
+    /// (for example, by acquiring a writer) also locks any other wrapper for the same side.
+    /// Use [`into_readable_writable`](Self::into_readable_writable) to obtain each side
+    /// exactly once.
     #[inline]
     pub fn writable(&self) -> WritableStream {
         WritableStream::from_raw(self.as_raw().writable())
     }
+
+    /// Consumes this `TransformStream`, returning its readable and writable side.
+    ///
+    /// Unlike [`readable`](Self::readable) and [`writable`](Self::writable), this hands out
+    /// each side only once, so it cannot accidentally be wrapped twice.
+    #[inline]
+    pub fn into_readable_writable(self) -> (ReadableStream, WritableStream) {
+        (self.readable(), self.writable())
+    }
+
+    /// Consumes this `TransformStream`, converting its writable side into a [`Sink`]
+    /// and its readable side into a [`Stream`].
+    ///
+    /// This locks both sides of the transform stream, so it can be driven entirely from Rust.
+    /// Chunks sent into the sink come out transformed from the stream.
+    ///
+    /// **Panics** if either side is already locked.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn into_codec(self) -> (IntoSink<'static>, IntoStream<'static>) {
+        let sink = self.writable().into_sink();
+        let stream = self.readable().into_stream();
+        (sink, stream)
+    }
 }
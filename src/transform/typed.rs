@@ -0,0 +1,71 @@
+use futures_util::future;
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use wasm_bindgen::JsValue;
+
+use super::TransformStream;
+
+/// A [`TransformStream`] with typed input and output chunks.
+///
+/// Input chunks of type `In` are converted to [`JsValue`]s before they are written
+/// to the writable side, and output chunks read from the readable side are converted
+/// back to `Out`. Use [`into_codec`](Self::into_codec) to obtain the typed sides.
+#[derive(Debug)]
+pub struct TypedTransformStream<In, Out> {
+    inner: TransformStream,
+    into_js: fn(In) -> JsValue,
+    from_js: fn(JsValue) -> Result<Out, JsValue>,
+}
+
+impl<In, Out> TypedTransformStream<In, Out> {
+    /// Creates a new `TypedTransformStream` from a [`TransformStream`], with the given
+    /// conversions for its input and output chunks.
+    ///
+    /// If `from_js` fails for an output chunk, its error is yielded by the
+    /// output [`Stream`](https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html).
+    #[inline]
+    pub fn new(
+        inner: TransformStream,
+        into_js: fn(In) -> JsValue,
+        from_js: fn(JsValue) -> Result<Out, JsValue>,
+    ) -> Self {
+        Self {
+            inner,
+            into_js,
+            from_js,
+        }
+    }
+
+    /// Acquires a reference to the untyped [`TransformStream`].
+    #[inline]
+    pub fn as_inner(&self) -> &TransformStream {
+        &self.inner
+    }
+
+    /// Consumes this `TypedTransformStream`, returning the untyped [`TransformStream`].
+    #[inline]
+    pub fn into_inner(self) -> TransformStream {
+        self.inner
+    }
+
+    /// Consumes this `TypedTransformStream`, converting its writable side into a [`Sink`]
+    /// of `In` chunks and its readable side into a [`Stream`] of `Out` chunks.
+    ///
+    /// This is the typed equivalent of [`TransformStream::into_codec`].
+    ///
+    /// **Panics** if either side is already locked.
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn into_codec(
+        self,
+    ) -> (
+        impl Sink<In, Error = JsValue>,
+        impl Stream<Item = Result<Out, JsValue>>,
+    ) {
+        let (into_js, from_js) = (self.into_js, self.from_js);
+        let (sink, stream) = self.inner.into_codec();
+        let sink = sink.with(move |chunk| future::ready(Ok(into_js(chunk))));
+        let stream = stream.map(move |result| result.and_then(from_js));
+        (sink, stream)
+    }
+}
@@ -1,3 +1,5 @@
+use core::future::Future;
+
 use js_sys::Promise;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
@@ -9,6 +11,42 @@ pub(crate) async fn promise_to_void_future(promise: Promise) -> Result<(), JsVal
     Ok(())
 }
 
+/// Turns a panic inside the given future into a rejection with a [`js_sys::Error`].
+///
+/// This is only enabled with the `panic-capture` feature when panics unwind.
+/// Otherwise, this returns the future as-is, so it compiles to nothing.
+#[cfg(all(feature = "panic-capture", panic = "unwind"))]
+pub(crate) async fn capture_panic<F>(fut: F) -> Result<JsValue, JsValue>
+where
+    F: Future<Output = Result<JsValue, JsValue>>,
+{
+    use futures_util::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    match AssertUnwindSafe(fut).catch_unwind().await {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.as_str()
+            } else {
+                "Box<dyn Any>"
+            };
+            Err(js_sys::Error::new(&format!("panicked at '{}'", message)).into())
+        }
+    }
+}
+
+#[cfg(not(all(feature = "panic-capture", panic = "unwind")))]
+#[inline(always)]
+pub(crate) fn capture_panic<F>(fut: F) -> F
+where
+    F: Future<Output = Result<JsValue, JsValue>>,
+{
+    fut
+}
+
 pub(crate) fn clamp_to_u32(value: usize) -> u32 {
     let wrapped = value as u32;
     let overflow = value != (wrapped as usize);
@@ -42,7 +80,15 @@ pub(crate) fn checked_cast_to_usize(value: u32) -> usize {
 }
 
 pub(crate) fn js_to_io_error(js_value: JsValue) -> std::io::Error {
+    js_to_named_io_error(js_value, None)
+}
+
+pub(crate) fn js_to_named_io_error(js_value: JsValue, name: Option<&str>) -> std::io::Error {
     let message = js_to_string(&js_value).unwrap_or_else(|| "Unknown error".to_string());
+    let message = match name {
+        Some(name) => format!("{}: {}", name, message),
+        None => message,
+    };
     std::io::Error::new(std::io::ErrorKind::Other, message)
 }
 
@@ -0,0 +1,51 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_util::Sink;
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+use super::IntoSink;
+
+/// A [`Sink`] for the [`into_byte_sink`](super::WritableStream::into_byte_sink) method.
+///
+/// This converts each `Vec<u8>` into a [`Uint8Array`] and writes it through
+/// the wrapped [`IntoSink`], which waits for the writer to become ready before every write.
+///
+/// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+#[must_use = "sinks do nothing unless polled"]
+#[derive(Debug)]
+pub(super) struct ByteSink<'writer> {
+    inner: IntoSink<'writer>,
+}
+
+impl<'writer> ByteSink<'writer> {
+    #[inline]
+    pub fn new(inner: IntoSink<'writer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Sink<Vec<u8>> for ByteSink<'_> {
+    type Error = JsValue;
+
+    #[inline]
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        let chunk = Uint8Array::from(item.as_slice());
+        Pin::new(&mut self.inner).start_send(chunk.into())
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
@@ -0,0 +1,131 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures_util::io::AsyncWrite;
+use futures_util::{ready, Sink};
+use js_sys::{Error as JsError, TypeError, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// A handle to the number of bytes written to a stream,
+/// as returned by [`from_async_write_counted`](super::WritableStream::from_async_write_counted).
+#[derive(Debug, Clone)]
+pub struct ByteCounter {
+    written: Rc<Cell<u64>>,
+    closed: Rc<Cell<bool>>,
+}
+
+impl ByteCounter {
+    /// Returns the number of bytes written to the `AsyncWrite` so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.written.get()
+    }
+
+    /// Returns the total number of bytes written to the `AsyncWrite`.
+    ///
+    /// This returns `None` until the stream has closed. If the stream errors
+    /// or is aborted instead, this keeps returning `None`.
+    pub fn total(&self) -> Option<u64> {
+        if self.closed.get() {
+            Some(self.written.get())
+        } else {
+            None
+        }
+    }
+}
+
+/// A [`Sink`] for the [`from_async_write_counted`](super::WritableStream::from_async_write_counted)
+/// method.
+///
+/// This writes the bytes of each [`Uint8Array`] chunk to the wrapped [`AsyncWrite`],
+/// counting every byte as it is accepted by the writer.
+///
+/// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+/// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
+pub(super) struct CountedWrite<W> {
+    writer: Pin<Box<W>>,
+    buffer: Vec<u8>,
+    pos: usize,
+    written: Rc<Cell<u64>>,
+    closed: Rc<Cell<bool>>,
+}
+
+impl<W: AsyncWrite> CountedWrite<W> {
+    pub fn new(writer: W) -> (Self, ByteCounter) {
+        let written = Rc::new(Cell::new(0));
+        let closed = Rc::new(Cell::new(false));
+        let counter = ByteCounter {
+            written: written.clone(),
+            closed: closed.clone(),
+        };
+        let sink = Self {
+            writer: Box::pin(writer),
+            buffer: Vec::new(),
+            pos: 0,
+            written,
+            closed,
+        };
+        (sink, counter)
+    }
+
+    fn poll_write_buffer(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+        while self.pos < self.buffer.len() {
+            let result = ready!(self
+                .writer
+                .as_mut()
+                .poll_write(cx, &self.buffer[self.pos..]));
+            match result {
+                Ok(0) => {
+                    return Poll::Ready(Err(JsError::new("failed to write whole chunk").into()))
+                }
+                Ok(n) => {
+                    self.pos += n;
+                    self.written.set(self.written.get() + n as u64);
+                }
+                Err(err) => return Poll::Ready(Err(JsError::new(&err.to_string()).into())),
+            }
+        }
+        self.buffer.clear();
+        self.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+// The writer is pinned in a box, so this sink can move freely.
+impl<W> Unpin for CountedWrite<W> {}
+
+impl<W: AsyncWrite> Sink<JsValue> for CountedWrite<W> {
+    type Error = JsValue;
+
+    #[inline]
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_write_buffer(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), Self::Error> {
+        let chunk = item
+            .dyn_into::<Uint8Array>()
+            .map_err(|_| TypeError::new("expected a Uint8Array chunk"))?;
+        self.buffer = chunk.to_vec();
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.poll_write_buffer(cx))?;
+        self.writer
+            .as_mut()
+            .poll_flush(cx)
+            .map_err(|err| JsError::new(&err.to_string()).into())
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.poll_write_buffer(cx))?;
+        ready!(self.writer.as_mut().poll_close(cx))
+            .map_err(|err| JsValue::from(JsError::new(&err.to_string())))?;
+        self.closed.set(true);
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,10 +1,15 @@
 use std::marker::PhantomData;
+use std::rc::Rc;
 
+use futures_util::future::try_join_all;
+use js_sys::Uint8Array;
 use wasm_bindgen::{throw_val, JsValue};
+use wasm_bindgen_futures::JsFuture;
 
 use crate::util::promise_to_void_future;
 
-use super::{sys, IntoAsyncWrite, IntoSink, WritableStream};
+use super::desired_size_updates::WriterNotify;
+use super::{sys, DesiredSizeUpdates, IntoAsyncWrite, IntoSink, WritableStream};
 
 /// A [`WritableStreamDefaultWriter`](https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter)
 /// that can be used to write chunks to a [`WritableStream`](WritableStream).
@@ -15,6 +20,7 @@ use super::{sys, IntoAsyncWrite, IntoSink, WritableStream};
 #[derive(Debug)]
 pub struct WritableStreamDefaultWriter<'stream> {
     raw: sys::WritableStreamDefaultWriter,
+    notify: Rc<WriterNotify>,
     _stream: PhantomData<&'stream mut WritableStream>,
 }
 
@@ -22,6 +28,7 @@ impl<'stream> WritableStreamDefaultWriter<'stream> {
     pub(crate) fn new(stream: &mut WritableStream) -> Result<Self, js_sys::Error> {
         Ok(Self {
             raw: stream.as_raw().get_writer()?,
+            notify: Rc::new(WriterNotify::default()),
             _stream: PhantomData,
         })
     }
@@ -68,12 +75,34 @@ impl<'stream> WritableStreamDefaultWriter<'stream> {
         promise_to_void_future(self.as_raw().ready()).await
     }
 
+    /// Returns a [`Stream`] of updates to the [desired size](Self::desired_size),
+    /// so a producer can react to changes in backpressure without polling.
+    ///
+    /// The stream yields the current desired size once the writer is [ready](Self::ready),
+    /// and again every time the desired size transitions from non-positive to positive.
+    /// When the stream closes, it yields the final desired size and then ends.
+    /// If the stream errors or the writer's lock is released, it ends without a final update.
+    ///
+    /// Backpressure only changes as a result of writing, closing or aborting the stream.
+    /// Such operations are noticed immediately if they go through this writer (or a [`Sink`] or
+    /// [`AsyncWrite`] created from it), but not if they are performed directly on the
+    /// [raw writer](Self::as_raw).
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    /// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
+    pub fn desired_size_updates(&self) -> DesiredSizeUpdates {
+        DesiredSizeUpdates::new(self.raw.clone(), self.notify.clone())
+    }
+
     /// [Aborts](https://streams.spec.whatwg.org/#abort-a-writable-stream) the stream,
     /// signaling that the producer can no longer successfully write to the stream.
     ///
     /// Equivalent to [`WritableStream.abort`](WritableStream::abort).
     pub async fn abort(&mut self) -> Result<(), JsValue> {
-        promise_to_void_future(self.as_raw().abort()).await
+        let promise = self.as_raw().abort();
+        self.notify_updates();
+        promise_to_void_future(promise).await
     }
 
     /// [Aborts](https://streams.spec.whatwg.org/#abort-a-writable-stream) the stream with the
@@ -81,7 +110,28 @@ impl<'stream> WritableStreamDefaultWriter<'stream> {
     ///
     /// Equivalent to [`WritableStream.abort_with_reason`](WritableStream::abort_with_reason).
     pub async fn abort_with_reason(&mut self, reason: &JsValue) -> Result<(), JsValue> {
-        promise_to_void_future(self.as_raw().abort_with_reason(reason)).await
+        let promise = self.as_raw().abort_with_reason(reason);
+        self.notify_updates();
+        promise_to_void_future(promise).await
+    }
+
+    /// [Aborts](https://streams.spec.whatwg.org/#abort-a-writable-stream) the stream, optionally
+    /// with the given `reason`, and waits until the stream is fully torn down.
+    ///
+    /// This behaves like [`abort`](Self::abort) (or [`abort_with_reason`](Self::abort_with_reason)
+    /// if a `reason` is given), but also waits for the writer to become [closed](Self::closed).
+    /// Since an aborted stream becomes errored, the rejection of `closed()` with the abort reason
+    /// is expected and is not reported as an error.
+    ///
+    /// This returns `Ok(())` once the stream is torn down, or `Err(error)` if aborting fails.
+    pub async fn abort_and_wait(&mut self, reason: Option<&JsValue>) -> Result<(), JsValue> {
+        match reason {
+            Some(reason) => self.abort_with_reason(reason).await?,
+            None => self.abort().await?,
+        }
+        // Aborting errors the stream, so closed() rejects with the abort reason
+        let _ = self.closed().await;
+        Ok(())
     }
 
     /// Writes the given `chunk` to the writable stream, by waiting until any previous writes
@@ -95,7 +145,43 @@ impl<'stream> WritableStreamDefaultWriter<'stream> {
     /// that the chunk has been accepted, and not necessarily that it is safely saved to
     /// its ultimate destination.
     pub async fn write(&mut self, chunk: JsValue) -> Result<(), JsValue> {
-        promise_to_void_future(self.as_raw().write_with_chunk(&chunk)).await
+        let promise = self.as_raw().write_with_chunk(&chunk);
+        self.notify_updates();
+        promise_to_void_future(promise).await
+    }
+
+    /// Writes all bytes of `data` to the writable stream, as [`Uint8Array`] chunks.
+    ///
+    /// Before writing each chunk, this waits until the writer is [ready](Self::ready), and then
+    /// writes a chunk of at most [`desired_size`](Self::desired_size) bytes. This respects the
+    /// stream's backpressure, without having to wait for each individual write to finish.
+    /// This is intended for streams with a
+    /// [`ByteLengthQueuingStrategy`](https://developer.mozilla.org/en-US/docs/Web/API/ByteLengthQueuingStrategy),
+    /// where the desired size is measured in bytes. With any other queuing strategy,
+    /// the chunks may be much smaller than necessary.
+    ///
+    /// This returns `Ok(())` once all chunks are written successfully,
+    /// or `Err(error)` if any write fails or the stream becomes errored.
+    ///
+    /// [`Uint8Array`]: js_sys::Uint8Array
+    pub async fn write_all_bytes(&mut self, mut data: &[u8]) -> Result<(), JsValue> {
+        let mut writes = Vec::new();
+        while !data.is_empty() {
+            self.ready().await?;
+            let desired_size = self.desired_size().unwrap_or_default();
+            // Always make progress, even if the desired size is fractional
+            let chunk_len = (desired_size as usize).clamp(1, data.len());
+            let (chunk, rest) = data.split_at(chunk_len);
+            let promise = self
+                .as_raw()
+                .write_with_chunk(&Uint8Array::from(chunk).into());
+            self.notify_updates();
+            // Wrap the promise right away, so a failed write is never an unhandled rejection
+            writes.push(JsFuture::from(promise));
+            data = rest;
+        }
+        try_join_all(writes).await?;
+        Ok(())
     }
 
     /// Closes the stream.
@@ -107,7 +193,14 @@ impl<'stream> WritableStreamDefaultWriter<'stream> {
     /// This returns `Ok(())` if all remaining chunks are successfully written and the stream
     /// successfully closes, or `Err(error)` if an error is encountered during this process.
     pub async fn close(&mut self) -> Result<(), JsValue> {
-        promise_to_void_future(self.as_raw().close()).await
+        let promise = self.as_raw().close();
+        self.notify_updates();
+        promise_to_void_future(promise).await
+    }
+
+    #[inline]
+    pub(super) fn notify_updates(&self) {
+        self.notify.notify();
     }
 
     /// Converts this `WritableStreamDefaultWriter` into a [`Sink`].
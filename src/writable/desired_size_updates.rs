@@ -0,0 +1,121 @@
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::stream::{FusedStream, Stream};
+use futures_util::FutureExt;
+use js_sys::{Object, Promise};
+use wasm_bindgen_futures::JsFuture;
+
+use super::sys;
+
+/// Wakes up any [`DesiredSizeUpdates`] streams whenever the writer may have changed
+/// its ready promise, i.e. after a write, close or abort.
+#[derive(Debug, Default)]
+pub(super) struct WriterNotify {
+    wakers: RefCell<Vec<Waker>>,
+}
+
+impl WriterNotify {
+    pub fn notify(&self) {
+        for waker in self.wakers.take() {
+            waker.wake();
+        }
+    }
+
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.borrow_mut();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+/// A [`Stream`] for the [`desired_size_updates`](super::WritableStreamDefaultWriter::desired_size_updates)
+/// method.
+///
+/// [`Stream`]: https://docs.rs/futures/0.3.30/futures/stream/trait.Stream.html
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct DesiredSizeUpdates {
+    raw: sys::WritableStreamDefaultWriter,
+    notify: Rc<WriterNotify>,
+    last_ready: Option<Promise>,
+    ready_fut: Option<JsFuture>,
+    closed_fut: JsFuture,
+    done: bool,
+}
+
+impl DesiredSizeUpdates {
+    pub(super) fn new(raw: sys::WritableStreamDefaultWriter, notify: Rc<WriterNotify>) -> Self {
+        let closed_fut = JsFuture::from(raw.closed());
+        Self {
+            raw,
+            notify,
+            last_ready: None,
+            ready_fut: None,
+            closed_fut,
+            done: false,
+        }
+    }
+
+    fn desired_size(&self) -> Option<f64> {
+        // Released writers throw, treat those the same as an errored stream.
+        self.raw.desired_size().unwrap_or(None)
+    }
+}
+
+impl FusedStream for DesiredSizeUpdates {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+impl Stream for DesiredSizeUpdates {
+    type Item = Option<f64>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        if let Poll::Ready(js_result) = self.closed_fut.poll_unpin(cx) {
+            // Stream closed (or errored, or the writer was released)
+            self.done = true;
+            return Poll::Ready(match js_result {
+                Ok(_) => Some(self.desired_size()),
+                Err(_) => None,
+            });
+        }
+
+        loop {
+            if let Some(ready_fut) = self.ready_fut.as_mut() {
+                // Wait for the current ready promise to resolve
+                let js_result = futures_util::ready!(ready_fut.poll_unpin(cx));
+                self.ready_fut = None;
+                return Poll::Ready(match js_result {
+                    Ok(_) => Some(self.desired_size()),
+                    Err(_) => {
+                        self.done = true;
+                        None
+                    }
+                });
+            }
+
+            let ready = self.raw.ready();
+            let is_same = match &self.last_ready {
+                Some(last_ready) => Object::is(last_ready, &ready),
+                None => false,
+            };
+            if is_same {
+                // Already reported this ready promise,
+                // wait until the writer replaces it with a new one
+                self.notify.register(cx.waker());
+                return Poll::Pending;
+            }
+            self.ready_fut = Some(JsFuture::from(ready.clone()));
+            self.last_ready = Some(ready);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::{ready, Sink};
+use wasm_bindgen::prelude::*;
+
+#[derive(Default)]
+struct Shared {
+    result: Option<Result<(), JsValue>>,
+    waker: Option<Waker>,
+}
+
+/// Reports the final state of a stream to its [`SinkDone`] future.
+#[derive(Clone)]
+pub(super) struct DoneReporter {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl DoneReporter {
+    /// Reports the final state, unless it was already reported before.
+    pub fn finish(&self, result: Result<(), JsValue>) {
+        let mut shared = self.shared.borrow_mut();
+        if shared.result.is_none() {
+            shared.result = Some(result);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// A [`Future`] for the [`from_sink_with_done`](super::WritableStream::from_sink_with_done)
+/// method.
+///
+/// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+#[must_use = "futures do nothing unless polled"]
+pub(super) struct SinkDone {
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Future for SinkDone {
+    type Output = Result<(), JsValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.borrow_mut();
+        match &shared.result {
+            Some(result) => Poll::Ready(result.clone()),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Wraps a [`Sink`], reporting to a [`SinkDone`] once it has been closed or has failed.
+pub(super) struct ReportDone<Si> {
+    sink: Pin<Box<Si>>,
+    reporter: DoneReporter,
+}
+
+impl<Si> ReportDone<Si> {
+    pub fn new(sink: Si) -> (Self, DoneReporter, SinkDone) {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let reporter = DoneReporter {
+            shared: shared.clone(),
+        };
+        let wrapper = ReportDone {
+            sink: Box::pin(sink),
+            reporter: reporter.clone(),
+        };
+        (wrapper, reporter, SinkDone { shared })
+    }
+
+    fn report<T>(&self, result: Result<T, JsValue>) -> Result<T, JsValue> {
+        if let Err(err) = &result {
+            // The stream becomes errored
+            self.reporter.finish(Err(err.clone()));
+        }
+        result
+    }
+}
+
+impl<Si> Sink<JsValue> for ReportDone<Si>
+where
+    Si: Sink<JsValue, Error = JsValue>,
+{
+    type Error = JsValue;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let result = ready!(self.sink.as_mut().poll_ready(cx));
+        Poll::Ready(self.report(result))
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), Self::Error> {
+        let result = self.sink.as_mut().start_send(item);
+        self.report(result)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let result = ready!(self.sink.as_mut().poll_flush(cx));
+        Poll::Ready(self.report(result))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let result = ready!(self.sink.as_mut().poll_close(cx));
+        if result.is_ok() {
+            self.reporter.finish(Ok(()));
+        }
+        Poll::Ready(self.report(result))
+    }
+}
@@ -7,7 +7,7 @@ use futures_util::sink::SinkExt;
 use js_sys::Uint8Array;
 use wasm_bindgen::JsValue;
 
-use crate::util::js_to_io_error;
+use crate::util::js_to_named_io_error;
 
 use super::IntoSink;
 
@@ -17,17 +17,40 @@ use super::IntoSink;
 /// When this `AsyncWrite` is dropped, it also drops its writer which in turn
 /// [releases its lock](https://streams.spec.whatwg.org/#release-a-lock).
 ///
+/// Every successful [`poll_write`] hands its bytes over to the stream right away, and releasing
+/// the lock does not discard writes that are still queued. So dropping this `AsyncWrite` without
+/// flushing does not lose any data, but any errors from those last writes go unnoticed.
+/// Call [`flush`] or [`close`] before dropping to observe them.
+///
 /// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
+/// [`poll_write`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html#tymethod.poll_write
+/// [`flush`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWriteExt.html#method.flush
+/// [`close`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWriteExt.html#method.close
 #[must_use = "writers do nothing unless polled"]
 #[derive(Debug)]
 pub struct IntoAsyncWrite<'writer> {
     sink: IntoSink<'writer>,
+    name: Option<String>,
 }
 
 impl<'writer> IntoAsyncWrite<'writer> {
     #[inline]
     pub(super) fn new(sink: IntoSink<'writer>) -> Self {
-        Self { sink }
+        Self { sink, name: None }
+    }
+
+    /// Sets a name for this `AsyncWrite`, which is prefixed to the message of any I/O error
+    /// that it returns.
+    ///
+    /// This can help to tell streams apart when debugging a pipeline with multiple streams.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    #[inline]
+    fn to_io_error(&self, js_value: JsValue) -> std::io::Error {
+        js_to_named_io_error(js_value, self.name.as_deref())
     }
 
     /// [Aborts](https://streams.spec.whatwg.org/#abort-a-writable-stream) the stream,
@@ -49,15 +72,19 @@ impl<'writer> AsyncWrite for IntoAsyncWrite<'writer> {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<std::io::Result<usize>> {
+        if buf.is_empty() {
+            // Nothing to write, don't send an empty chunk to the stream
+            return Poll::Ready(Ok(0));
+        }
         ready!(self
             .as_mut()
             .sink
             .poll_ready_unpin(cx)
-            .map_err(js_to_io_error))?;
+            .map_err(|err| self.to_io_error(err)))?;
         self.as_mut()
             .sink
             .start_send_unpin(Uint8Array::from(buf).into())
-            .map_err(js_to_io_error)?;
+            .map_err(|err| self.to_io_error(err))?;
         Poll::Ready(Ok(buf.len()))
     }
 
@@ -65,13 +92,13 @@ impl<'writer> AsyncWrite for IntoAsyncWrite<'writer> {
         self.as_mut()
             .sink
             .poll_flush_unpin(cx)
-            .map_err(js_to_io_error)
+            .map_err(|err| self.to_io_error(err))
     }
 
     fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
         self.as_mut()
             .sink
             .poll_close_unpin(cx)
-            .map_err(js_to_io_error)
+            .map_err(|err| self.to_io_error(err))
     }
 }
@@ -3,6 +3,7 @@ use core::task::{Context, Poll};
 
 use futures_util::Sink;
 use futures_util::{ready, FutureExt};
+use js_sys::TypeError;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
@@ -22,6 +23,8 @@ pub struct IntoSink<'writer> {
     ready_fut: Option<JsFuture>,
     write_fut: Option<JsFuture>,
     close_fut: Option<JsFuture>,
+    // The error that caused the writer to be dropped, if any
+    error: Option<JsValue>,
 }
 
 impl<'writer> IntoSink<'writer> {
@@ -32,6 +35,7 @@ impl<'writer> IntoSink<'writer> {
             ready_fut: None,
             write_fut: None,
             close_fut: None,
+            error: None,
         }
     }
 
@@ -52,12 +56,44 @@ impl<'writer> IntoSink<'writer> {
             None => Ok(()),
         }
     }
+
+    /// [Releases](https://streams.spec.whatwg.org/#release-a-lock) the writer's lock
+    /// on the stream, without closing or aborting it.
+    ///
+    /// This is equivalent to dropping the sink, but makes the intent explicit. Afterwards,
+    /// a new writer can be acquired to continue writing to the stream.
+    ///
+    /// Any chunks that were sent but not yet flushed are still written to the stream,
+    /// but their completion can no longer be observed through this sink.
+    #[inline]
+    pub fn release(self) {
+        drop(self)
+    }
+
+    /// Drops the writer after the stream encountered an error,
+    /// remembering the error for subsequent operations.
+    fn fail(&mut self, error: JsValue) -> JsValue {
+        self.writer = None;
+        self.error = Some(error.clone());
+        error
+    }
+
+    /// Returns the error for an operation after the writer was dropped.
+    fn dropped_error(&self) -> JsValue {
+        // If the stream errored, report that error.
+        // Otherwise, the stream was closed.
+        self.error.clone().unwrap_or_else(closed_error)
+    }
 }
 
 impl<'writer> Sink<JsValue> for IntoSink<'writer> {
     type Error = JsValue;
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.close_fut.is_some() {
+            // Stream is closing
+            return Poll::Ready(Err(closed_error()));
+        }
         let ready_fut = match self.ready_fut.as_mut() {
             Some(fut) => fut,
             None => match &self.writer {
@@ -67,9 +103,8 @@ impl<'writer> Sink<JsValue> for IntoSink<'writer> {
                     self.ready_fut.insert(fut)
                 }
                 None => {
-                    // Writer was already dropped
-                    // TODO Return error?
-                    return Poll::Ready(Ok(()));
+                    // Writer was already dropped, because the stream closed or errored
+                    return Poll::Ready(Err(self.dropped_error()));
                 }
             },
         };
@@ -86,23 +121,27 @@ impl<'writer> Sink<JsValue> for IntoSink<'writer> {
             }
             Err(js_value) => {
                 // Error, drop writer
-                self.writer = None;
-                Err(js_value)
+                Err(self.fail(js_value))
             }
         })
     }
 
     fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), Self::Error> {
         match &self.writer {
+            Some(_) if self.close_fut.is_some() => {
+                // Stream is closing
+                Err(closed_error())
+            }
             Some(writer) => {
                 let fut = JsFuture::from(writer.as_raw().write_with_chunk(&item));
+                writer.notify_updates();
                 // Set or replace the pending write future
                 self.write_fut = Some(fut);
                 Ok(())
             }
             None => {
-                // TODO Return error?
-                Ok(())
+                // Writer was already dropped, because the stream closed or errored
+                Err(self.dropped_error())
             }
         }
     }
@@ -128,8 +167,7 @@ impl<'writer> Sink<JsValue> for IntoSink<'writer> {
             }
             Err(js_value) => {
                 // Error, drop writer
-                self.writer = None;
-                Err(js_value)
+                Err(self.fail(js_value))
             }
         })
     }
@@ -142,12 +180,16 @@ impl<'writer> Sink<JsValue> for IntoSink<'writer> {
                     // No pending close future
                     // Start closing the stream and create future from close promise
                     let fut = JsFuture::from(writer.as_raw().close());
+                    writer.notify_updates();
                     self.close_fut.insert(fut)
                 }
                 None => {
-                    // Writer was already dropped
-                    // TODO Return error?
-                    return Poll::Ready(Ok(()));
+                    // Writer was already dropped. If the stream errored, report that error.
+                    // Otherwise, the stream is already closed.
+                    return Poll::Ready(match self.error.clone() {
+                        Some(error) => Err(error),
+                        None => Ok(()),
+                    });
                 }
             },
         };
@@ -163,7 +205,11 @@ impl<'writer> Sink<JsValue> for IntoSink<'writer> {
                 debug_assert!(js_value.is_undefined());
                 Ok(())
             }
-            Err(js_value) => Err(js_value),
+            Err(js_value) => Err(self.fail(js_value)),
         })
     }
 }
+
+fn closed_error() -> JsValue {
+    TypeError::new("Cannot write to a closing or closed stream").into()
+}
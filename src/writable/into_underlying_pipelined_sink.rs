@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use futures_util::FutureExt;
+use js_sys::Promise;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+use crate::util::capture_panic;
+
+type WriteFn = dyn FnMut(JsValue) -> Pin<Box<dyn Future<Output = Result<(), JsValue>>>>;
+
+#[wasm_bindgen]
+pub(crate) struct IntoUnderlyingPipelinedSink {
+    inner: Rc<RefCell<Inner>>,
+}
+
+impl IntoUnderlyingPipelinedSink {
+    pub fn new(write: Box<WriteFn>, max_in_flight: usize) -> Self {
+        IntoUnderlyingPipelinedSink {
+            inner: Rc::new(RefCell::new(Inner::new(write, max_in_flight))),
+        }
+    }
+}
+
+#[allow(clippy::await_holding_refcell_ref)]
+#[wasm_bindgen]
+impl IntoUnderlyingPipelinedSink {
+    pub fn write(&mut self, chunk: JsValue) -> Promise {
+        let inner = self.inner.clone();
+        future_to_promise(capture_panic(async move {
+            // This mutable borrow can never panic, since the WritableStream always queues
+            // each operation on the underlying sink.
+            let mut inner = inner.try_borrow_mut().unwrap_throw();
+            inner.write(chunk).await.map(|_| JsValue::undefined())
+        }))
+    }
+
+    pub fn close(self) -> Promise {
+        future_to_promise(capture_panic(async move {
+            let mut inner = self.inner.try_borrow_mut().unwrap_throw();
+            inner.close().await.map(|_| JsValue::undefined())
+        }))
+    }
+
+    pub fn abort(self, _reason: JsValue) -> Promise {
+        // Drop the write function and stop waiting for in-flight writes.
+        // These keep running in the background, but their results are ignored.
+        drop(self);
+        Promise::resolve(&JsValue::undefined())
+    }
+}
+
+struct Inner {
+    write: Box<WriteFn>,
+    in_flight: FuturesUnordered<JsFuture>,
+    max_in_flight: usize,
+}
+
+impl Inner {
+    fn new(write: Box<WriteFn>, max_in_flight: usize) -> Self {
+        Inner {
+            write,
+            in_flight: FuturesUnordered::new(),
+            max_in_flight,
+        }
+    }
+
+    async fn write(&mut self, chunk: JsValue) -> Result<(), JsValue> {
+        // Report any writes that have already failed.
+        while let Some(Some(result)) = self.in_flight.next().now_or_never() {
+            result?;
+        }
+        // Wait for a slot to become available.
+        while self.in_flight.len() >= self.max_in_flight {
+            if let Some(result) = self.in_flight.next().await {
+                result?;
+            }
+        }
+        // Start the write in the background, so it keeps making progress
+        // even while no other operation is waiting for it.
+        let fut = (self.write)(chunk).map(|result| result.map(|_| JsValue::undefined()));
+        let promise = future_to_promise(capture_panic(fut));
+        self.in_flight.push(JsFuture::from(promise));
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<(), JsValue> {
+        // Wait for all in-flight writes to complete.
+        while let Some(result) = self.in_flight.next().await {
+            result?;
+        }
+        Ok(())
+    }
+}
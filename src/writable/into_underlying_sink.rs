@@ -1,11 +1,20 @@
 use std::cell::RefCell;
+use std::future::Future;
 use std::pin::Pin;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::task::{Poll, Waker};
 
+use futures_util::future::{poll_fn, select, Either};
 use futures_util::{Sink, SinkExt};
 use js_sys::Promise;
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::future_to_promise;
+use wasm_bindgen_futures::{future_to_promise, spawn_local};
+
+use crate::timer::sleep;
+use crate::util::capture_panic;
+
+pub(super) type AbortFn = dyn FnOnce(JsValue) -> Pin<Box<dyn Future<Output = Result<(), JsValue>>>>;
+pub(super) type IsTransientFn = dyn Fn(&JsValue) -> bool;
 
 #[wasm_bindgen]
 pub(crate) struct IntoUnderlyingSink {
@@ -18,6 +27,37 @@ impl IntoUnderlyingSink {
             inner: Rc::new(RefCell::new(Inner::new(sink))),
         }
     }
+
+    /// Rejects any write that takes longer than `ms` milliseconds,
+    /// which errors the stream and drops the sink.
+    pub fn with_write_timeout(self, ms: u32) -> Self {
+        self.inner.borrow_mut().write_timeout = Some(ms);
+        self
+    }
+
+    /// Retries every write up to `max_retries` times if it fails with an error
+    /// for which `is_transient` returns `true`. Once the sink has accepted the chunk,
+    /// only the flush is retried.
+    pub fn with_retry(self, max_retries: u32, is_transient: Box<IsTransientFn>) -> Self {
+        self.inner.borrow_mut().retry = Some((max_retries, is_transient));
+        self
+    }
+
+    /// Stops flushing the sink after every write, and instead flushes it every `ms` milliseconds
+    /// (as well as on close). If a periodic flush fails, the error is reported on the next write
+    /// or on close.
+    pub fn with_autoflush(self, ms: u32) -> Self {
+        self.inner.borrow_mut().autoflush = true;
+        spawn_local(autoflush_loop(Rc::downgrade(&self.inner), ms));
+        self
+    }
+
+    /// Calls `on_abort` with the abort reason after dropping the sink when the stream
+    /// is aborted, and reports its error (if any) as the result of the abort.
+    pub fn with_abort_handler(self, on_abort: Box<AbortFn>) -> Self {
+        self.inner.borrow_mut().on_abort = Some(on_abort);
+        self
+    }
 }
 
 #[allow(clippy::await_holding_refcell_ref)]
@@ -25,45 +65,66 @@ impl IntoUnderlyingSink {
 impl IntoUnderlyingSink {
     pub fn write(&mut self, chunk: JsValue) -> Promise {
         let inner = self.inner.clone();
-        future_to_promise(async move {
+        future_to_promise(capture_panic(async move {
             // This mutable borrow can never panic, since the WritableStream always queues
             // each operation on the underlying sink.
             let mut inner = inner.try_borrow_mut().unwrap_throw();
             inner.write(chunk).await.map(|_| JsValue::undefined())
-        })
+        }))
     }
 
     pub fn close(self) -> Promise {
-        future_to_promise(async move {
+        future_to_promise(capture_panic(async move {
             let mut inner = self.inner.try_borrow_mut().unwrap_throw();
             inner.close().await.map(|_| JsValue::undefined())
-        })
+        }))
     }
 
     pub fn abort(self, reason: JsValue) -> Promise {
-        future_to_promise(async move {
+        future_to_promise(capture_panic(async move {
             let mut inner = self.inner.try_borrow_mut().unwrap_throw();
             inner.abort(reason).await.map(|_| JsValue::undefined())
-        })
+        }))
     }
 }
 
 struct Inner {
     sink: Option<Pin<Box<dyn Sink<JsValue, Error = JsValue>>>>,
+    write_timeout: Option<u32>,
+    on_abort: Option<Box<AbortFn>>,
+    retry: Option<(u32, Box<IsTransientFn>)>,
+    autoflush: bool,
+    // The waker of a periodic flush that is still pending
+    flush_waker: Option<Waker>,
+    flush_error: Option<JsValue>,
 }
 
 impl Inner {
     fn new(sink: Box<dyn Sink<JsValue, Error = JsValue>>) -> Self {
         Inner {
             sink: Some(sink.into()),
+            write_timeout: None,
+            on_abort: None,
+            retry: None,
+            autoflush: false,
+            flush_waker: None,
+            flush_error: None,
         }
     }
 
     async fn write(&mut self, chunk: JsValue) -> Result<(), JsValue> {
-        // The stream should still exist, since write() will not be called again
-        // after the sink has closed, aborted or encountered an error.
-        let sink = self.sink.as_mut().unwrap_throw();
-        match sink.send(chunk).await {
+        self.take_over_flush()?;
+        let result = match self.write_timeout {
+            Some(ms) => match select(Box::pin(self.send(chunk)), sleep(ms)).await {
+                Either::Left((result, _)) => result,
+                Either::Right(((), _)) => {
+                    let message = format!("write timed out after {} ms", ms);
+                    Err(js_sys::Error::new(&message).into())
+                }
+            },
+            None => self.send(chunk).await,
+        };
+        match result {
             Ok(()) => Ok(()),
             Err(err) => {
                 // The stream encountered an error, drop it.
@@ -73,12 +134,107 @@ impl Inner {
         }
     }
 
+    async fn send(&mut self, chunk: JsValue) -> Result<(), JsValue> {
+        // The stream should still exist, since write() will not be called again
+        // after the sink has closed, aborted or encountered an error.
+        let sink = self.sink.as_mut().unwrap_throw();
+        let mut retries = 0;
+        let mut should_retry = |err: &JsValue| match &self.retry {
+            Some((max_retries, is_transient)) if retries < *max_retries && is_transient(err) => {
+                retries += 1;
+                true
+            }
+            _ => false,
+        };
+        // Transient error while the sink has not yet accepted the chunk, send it again.
+        loop {
+            match sink.feed(chunk.clone()).await {
+                Ok(()) => break,
+                Err(err) if should_retry(&err) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        if self.autoflush {
+            // Don't flush after every write, leave that to the periodic flush.
+            return Ok(());
+        }
+        // The sink has already accepted the chunk, so only retry the flush
+        // to avoid sending the same chunk twice.
+        loop {
+            match sink.flush().await {
+                Ok(()) => return Ok(()),
+                Err(err) if should_retry(&err) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Stops a pending periodic flush, since this operation now owns the sink.
+    /// If a previous periodic flush failed, drops the sink and returns its error.
+    fn take_over_flush(&mut self) -> Result<(), JsValue> {
+        if let Some(waker) = self.flush_waker.take() {
+            waker.wake();
+        }
+        match self.flush_error.take() {
+            Some(err) => {
+                self.sink = None;
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+
     async fn close(&mut self) -> Result<(), JsValue> {
+        self.take_over_flush()?;
         self.sink.take().unwrap_throw().close().await
     }
 
-    async fn abort(&mut self, _reason: JsValue) -> Result<(), JsValue> {
+    async fn abort(&mut self, reason: JsValue) -> Result<(), JsValue> {
+        // Any error from a periodic flush is superseded by the abort.
+        let _ = self.take_over_flush();
         self.sink = None;
-        Ok(())
+        match self.on_abort.take() {
+            Some(on_abort) => on_abort(reason).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Flushes the sink every `ms` milliseconds, until the sink is dropped.
+async fn autoflush_loop(inner: Weak<RefCell<Inner>>, ms: u32) {
+    loop {
+        sleep(ms).await;
+        let inner = match inner.upgrade() {
+            Some(inner) => inner,
+            None => return,
+        };
+        let done = poll_fn(|cx| {
+            let mut inner = match inner.try_borrow_mut() {
+                Ok(inner) => inner,
+                // Another operation is in progress, skip this flush.
+                Err(_) => return Poll::Ready(false),
+            };
+            let inner = &mut *inner;
+            let sink = match inner.sink.as_mut() {
+                Some(sink) if inner.flush_error.is_none() => sink,
+                // The sink was dropped or has failed, stop flushing.
+                _ => return Poll::Ready(true),
+            };
+            match sink.as_mut().poll_flush(cx) {
+                Poll::Ready(result) => {
+                    inner.flush_waker = None;
+                    inner.flush_error = result.err();
+                    Poll::Ready(false)
+                }
+                Poll::Pending => {
+                    inner.flush_waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await;
+        if done {
+            return;
+        }
     }
 }
@@ -1,20 +1,40 @@
 //! Bindings and conversions for
 //! [writable streams](https://developer.mozilla.org/en-US/docs/Web/API/WritableStream).
 
-use futures_util::Sink;
+use std::future::Future;
+
+use futures_util::io::AsyncWrite;
+use futures_util::sink::unfold;
+use futures_util::{future, FutureExt, Sink, SinkExt};
 use wasm_bindgen::prelude::*;
 
+use byte_sink::ByteSink;
+pub use counted_write::ByteCounter;
+use counted_write::CountedWrite;
 pub use default_writer::WritableStreamDefaultWriter;
+pub use desired_size_updates::DesiredSizeUpdates;
+use done_sink::ReportDone;
 pub use into_async_write::IntoAsyncWrite;
 pub use into_sink::IntoSink;
+use into_underlying_pipelined_sink::IntoUnderlyingPipelinedSink;
 use into_underlying_sink::IntoUnderlyingSink;
+pub use recover_sink::RecoverSink;
+use recover_sink::ReturnOnClose;
+pub use sink_options::SinkOptions;
 
 use crate::util::promise_to_void_future;
 
+mod byte_sink;
+mod counted_write;
 mod default_writer;
+mod desired_size_updates;
+mod done_sink;
 mod into_async_write;
 mod into_sink;
+mod into_underlying_pipelined_sink;
 mod into_underlying_sink;
+mod recover_sink;
+mod sink_options;
 pub mod sys;
 
 /// A [`WritableStream`](https://developer.mozilla.org/en-US/docs/Web/API/WritableStream).
@@ -29,13 +49,14 @@ pub mod sys;
 #[derive(Debug)]
 pub struct WritableStream {
     raw: sys::WritableStream,
+    label: Option<String>,
 }
 
 impl WritableStream {
     /// Creates a new `WritableStream` from a [JavaScript stream](sys::WritableStream).
     #[inline]
     pub fn from_raw(raw: sys::WritableStream) -> Self {
-        Self { raw }
+        Self { raw, label: None }
     }
 
     /// Creates a new `WritableStream` from a [`Sink`].
@@ -51,13 +72,263 @@ impl WritableStream {
     where
         Si: Sink<JsValue, Error = JsValue> + 'static,
     {
-        let sink = IntoUnderlyingSink::new(Box::new(sink));
+        Self::from_underlying_sink(IntoUnderlyingSink::new(Box::new(sink)))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], configured with the given [`SinkOptions`].
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), with any combination of the options
+    /// offered by the other `from_sink_*` constructors, such as a write timeout together with
+    /// retries for transient errors.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_with_options<Si>(sink: Si, options: SinkOptions) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+    {
+        Self::from_underlying_sink(options.into_underlying_sink(Box::new(sink)))
+    }
+
+    fn from_underlying_sink(sink: IntoUnderlyingSink) -> Self {
         // Use the default queuing strategy (with a HWM of 1 chunk).
         // We shouldn't set HWM to 0, since that would break piping to the writable stream.
         let raw = sys::WritableStreamExt::new_with_into_underlying_sink(sink).unchecked_into();
         Self::from_raw(raw)
     }
 
+    /// Creates a new `WritableStream` from a [`Sink`], transforming every chunk with `f`
+    /// before it is sent to the sink.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink) with a sink wrapped by [`with`],
+    /// but is more convenient for simple synchronous transforms. If `f` returns an error,
+    /// the write is rejected with that error, which errors the stream.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    /// [`with`]: https://docs.rs/futures/0.3.30/futures/sink/trait.SinkExt.html#method.with
+    pub fn from_sink_with_transform<Si, F>(sink: Si, mut f: F) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+        F: FnMut(JsValue) -> Result<JsValue, JsValue> + 'static,
+    {
+        Self::from_sink(sink.with(move |chunk| future::ready(f(chunk))))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], checking every chunk with `validate`
+    /// before it is sent to the sink.
+    ///
+    /// If `validate` returns an error, the write is rejected with that error and the chunk
+    /// never reaches the sink. This errors the stream, and the sink is dropped.
+    /// This can be used to ensure that a byte sink only ever receives `Uint8Array` chunks.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_validated<Si, F>(sink: Si, validate: F) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+        F: Fn(&JsValue) -> Result<(), JsValue> + 'static,
+    {
+        Self::from_sink_with_transform(sink, move |chunk| {
+            validate(&chunk)?;
+            Ok(chunk)
+        })
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], where every write must complete
+    /// within `ms` milliseconds.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), except that if sending a chunk
+    /// to the sink takes longer than `ms` milliseconds, the write is rejected with an `Error`.
+    /// This errors the stream, and the sink is dropped.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_with_write_timeout<Si>(sink: Si, ms: u32) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+    {
+        Self::from_sink_with_options(sink, SinkOptions::new().write_timeout(ms))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], which is flushed every `interval_ms`
+    /// milliseconds.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), except that the sink is not flushed
+    /// after every write. Instead, it is flushed periodically on a timer, and when the stream
+    /// is closed. This allows a buffering sink to batch up multiple writes, while still bounding
+    /// the time that data can stay in its buffer. Note that a write may complete before its chunk
+    /// is flushed.
+    ///
+    /// If a periodic flush fails, the next write (or closing the stream) is rejected with
+    /// that error. This errors the stream, and the sink is dropped.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_autoflush<Si>(sink: Si, interval_ms: u32) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+    {
+        Self::from_sink_with_options(sink, SinkOptions::new().autoflush(interval_ms))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], where every failed write is retried
+    /// up to `max_retries` times if its error is transient.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), except that if sending a chunk to the
+    /// sink fails with an error for which `is_transient` returns `true`, the same chunk is sent
+    /// again. If the sink has already accepted the chunk and only flushing it fails, then only
+    /// the flush is retried, so the sink never receives the same chunk twice.
+    /// Only once all retries have failed, or once the sink fails with a non-transient
+    /// error, is the write rejected. This errors the stream, and the sink is dropped.
+    ///
+    /// Note that the sink must remain usable after returning a transient error.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_with_retry<Si, F>(sink: Si, max_retries: u32, is_transient: F) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+        F: Fn(&JsValue) -> bool + 'static,
+    {
+        Self::from_sink_with_options(sink, SinkOptions::new().retry(max_retries, is_transient))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], with a custom handler for when the stream
+    /// is [aborted](https://streams.spec.whatwg.org/#abort-a-writable-stream).
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), except that after the sink is dropped
+    /// because the stream is aborted, `on_abort` is called with the abort reason. If the
+    /// returned future fails, then its error is returned from [`abort`](Self::abort) (and
+    /// [`abort_with_reason`](Self::abort_with_reason)) instead of `Ok(())`.
+    /// This allows reporting a failure to clean up the sink's resources.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn from_sink_with_abort_handler<Si, F, Fut>(sink: Si, on_abort: F) -> Self
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+        F: FnOnce(JsValue) -> Fut + 'static,
+        Fut: Future<Output = Result<(), JsValue>> + 'static,
+    {
+        Self::from_sink_with_options(sink, SinkOptions::new().abort_handler(on_abort))
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], along with a future that gives back
+    /// the sink once the stream has closed.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink), except that the sink is not dropped
+    /// after it has been closed. This allows reusing a sink that holds on to a resource,
+    /// such as a file handle.
+    ///
+    /// The returned [`RecoverSink`] resolves with `Some(sink)` as soon as the sink's
+    /// [`poll_close`] completes successfully, which happens right before the stream itself
+    /// becomes closed. If the stream is aborted or errors instead, it resolves with `None`.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    /// [`poll_close`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html#tymethod.poll_close
+    pub fn from_sink_recoverable<Si>(sink: Si) -> (Self, RecoverSink<Si>)
+    where
+        Si: Sink<JsValue, Error = JsValue> + Unpin + 'static,
+    {
+        let (sink, recover) = ReturnOnClose::new(sink);
+        (Self::from_sink(sink), recover)
+    }
+
+    /// Creates a new `WritableStream` from a [`Sink`], along with a future that completes
+    /// once the stream has closed or errored.
+    ///
+    /// This behaves like [`from_sink`](Self::from_sink). The returned future resolves with
+    /// `Ok(())` as soon as the sink's [`poll_close`] completes successfully, or with an error
+    /// if the sink fails or if the stream is
+    /// [aborted](https://streams.spec.whatwg.org/#abort-a-writable-stream) (with the abort
+    /// reason). This lets a producer wait for the final state of the sink, for example after
+    /// handing the stream to [`pipe_to`](crate::ReadableStream::pipe_to), without having to
+    /// hold on to a writer.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    /// [`poll_close`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html#tymethod.poll_close
+    pub fn from_sink_with_done<Si>(sink: Si) -> (Self, impl Future<Output = Result<(), JsValue>>)
+    where
+        Si: Sink<JsValue, Error = JsValue> + 'static,
+    {
+        let (sink, reporter, done) = ReportDone::new(sink);
+        let stream = Self::from_sink_with_abort_handler(sink, move |reason| {
+            reporter.finish(Err(reason));
+            future::ready(Ok(()))
+        });
+        (stream, done)
+    }
+
+    /// Creates a new `WritableStream` that calls `f` for every chunk written to the stream.
+    ///
+    /// The returned future must complete once the chunk has been handled. This behaves like
+    /// [`from_sink`](Self::from_sink) with a sink created by [`unfold`], so the next chunk is only
+    /// passed to `f` after the previous future has completed. If a future fails, the stream
+    /// becomes errored and `f` is dropped.
+    ///
+    /// [`unfold`]: https://docs.rs/futures/0.3.30/futures/sink/fn.unfold.html
+    pub fn from_write_fn<F, Fut>(mut f: F) -> Self
+    where
+        F: FnMut(JsValue) -> Fut + 'static,
+        Fut: Future<Output = Result<(), JsValue>> + 'static,
+    {
+        Self::from_sink(unfold((), move |(), chunk| f(chunk)))
+    }
+
+    /// Creates a new `WritableStream` that writes the bytes of every [`Uint8Array`] chunk to an
+    /// [`AsyncWrite`], along with a [`ByteCounter`] to retrieve the number of bytes written.
+    ///
+    /// Each write completes once all bytes of its chunk have been accepted by the `AsyncWrite`,
+    /// and closing the stream [closes][AsyncWrite::poll_close] the `AsyncWrite`. Once the stream
+    /// has closed, [`ByteCounter::total`] returns the total number of bytes written, which can be
+    /// used to verify the size of an upload. If a chunk is not a `Uint8Array`, or if the
+    /// `AsyncWrite` fails, the write is rejected. This errors the stream, and the `AsyncWrite`
+    /// is dropped.
+    ///
+    /// [`Uint8Array`]: js_sys::Uint8Array
+    /// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
+    /// [AsyncWrite::poll_close]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html#tymethod.poll_close
+    pub fn from_async_write_counted<W>(writer: W) -> (Self, ByteCounter)
+    where
+        W: AsyncWrite + 'static,
+    {
+        let (sink, counter) = CountedWrite::new(writer);
+        (Self::from_sink(sink), counter)
+    }
+
+    /// Creates a new `WritableStream` that can have multiple writes in flight at the same time.
+    ///
+    /// For every chunk written to the stream, `write` is called with that chunk and must return
+    /// a future that completes once the chunk is acknowledged. Unlike [`from_sink`](Self::from_sink),
+    /// the stream does not wait for this future before accepting the next chunk, so a
+    /// pipelined protocol can send the next request before the previous one is acknowledged.
+    /// At most `max_in_flight` writes are pending at any time; further writes wait for a slot.
+    ///
+    /// `write` is always called in the same order as the chunks were written, but the returned
+    /// futures may complete in any order. If any of them fails, the stream becomes errored
+    /// at the next write or on close. Closing the stream waits for all in-flight writes.
+    /// Aborting the stream does not cancel writes that are already in flight.
+    ///
+    /// **Panics** if `max_in_flight` is zero.
+    pub fn from_pipelined<F, Fut>(mut write: F, max_in_flight: usize) -> Self
+    where
+        F: FnMut(JsValue) -> Fut + 'static,
+        Fut: Future<Output = Result<(), JsValue>> + 'static,
+    {
+        assert!(max_in_flight > 0, "max_in_flight must be greater than zero");
+        let write = Box::new(move |chunk| write(chunk).boxed_local());
+        let sink = IntoUnderlyingPipelinedSink::new(write, max_in_flight);
+        let raw =
+            sys::WritableStreamExt::new_with_into_underlying_pipelined_sink(sink).unchecked_into();
+        Self::from_raw(raw)
+    }
+
+    /// Sets a label for this `WritableStream`, which is included in its [`Debug`] output.
+    ///
+    /// The label is also used as the name for an [`IntoAsyncWrite`] created from this stream,
+    /// so it is prefixed to the message of any I/O error that it returns.
+    /// This can help to tell streams apart when debugging an application with multiple streams.
+    ///
+    /// [`Debug`]: std::fmt::Debug
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
     /// Acquires a reference to the underlying [JavaScript stream](sys::WritableStream).
     #[inline]
     pub fn as_raw(&self) -> &sys::WritableStream {
@@ -76,6 +347,21 @@ impl WritableStream {
         self.as_raw().locked()
     }
 
+    /// Waits until the stream is no longer applying backpressure.
+    ///
+    /// This can be used to wait until the destination is ready before piping to it.
+    /// It acquires a writer, waits for it to become [ready](WritableStreamDefaultWriter::ready),
+    /// and then releases the writer again. While waiting, the stream is
+    /// [locked](https://streams.spec.whatwg.org/#lock) to that writer.
+    ///
+    /// This returns an error if the stream errors while waiting.
+    ///
+    /// **Panics** if the stream is already locked to a writer.
+    pub async fn wait_ready(&mut self) -> Result<(), JsValue> {
+        let writer = self.get_writer();
+        writer.ready().await
+    }
+
     /// [Aborts](https://streams.spec.whatwg.org/#abort-a-writable-stream) the stream,
     /// signaling that the producer can no longer successfully write to the stream
     /// and it is to be immediately moved to an errored state, with any queued-up writes discarded.
@@ -152,6 +438,35 @@ impl WritableStream {
         Ok(writer.into_sink())
     }
 
+    /// Converts this `WritableStream` into a [`Sink`] of byte vectors.
+    ///
+    /// The writable stream must accept [`Uint8Array`](js_sys::Uint8Array) chunks.
+    /// Each `Vec<u8>` is copied into a new `Uint8Array` before it is written, and the sink
+    /// waits for the writer to become [ready](WritableStreamDefaultWriter::ready) before
+    /// accepting the next item.
+    ///
+    /// **Panics** if the stream is already locked to a writer.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn into_byte_sink(self) -> impl Sink<Vec<u8>, Error = JsValue> {
+        ByteSink::new(self.into_sink())
+    }
+
+    /// Converts this `WritableStream` into a [`Sink`] of strings, encoded as UTF-8.
+    ///
+    /// The writable stream must accept [`Uint8Array`](js_sys::Uint8Array) chunks.
+    /// This behaves like [`into_byte_sink`](Self::into_byte_sink), with each `String` written
+    /// as its UTF-8 bytes. This avoids having to pipe through a `TextEncoderStream`
+    /// when writing text to a byte stream.
+    ///
+    /// **Panics** if the stream is already locked to a writer.
+    ///
+    /// [`Sink`]: https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html
+    pub fn into_utf8_sink(self) -> impl Sink<String, Error = JsValue> {
+        self.into_byte_sink()
+            .with(|string: String| future::ready(Ok(string.into_bytes())))
+    }
+
     /// Converts this `WritableStream` into an [`AsyncWrite`].
     ///
     /// The writable stream must accept [`Uint8Array`](js_sys::Uint8Array) chunks.
@@ -174,7 +489,12 @@ impl WritableStream {
     ///
     /// [`AsyncWrite`]: https://docs.rs/futures/0.3.30/futures/io/trait.AsyncWrite.html
     pub fn try_into_async_write(self) -> Result<IntoAsyncWrite<'static>, (js_sys::Error, Self)> {
-        Ok(IntoAsyncWrite::new(self.try_into_sink()?))
+        let label = self.label.clone();
+        let async_write = IntoAsyncWrite::new(self.try_into_sink()?);
+        Ok(match label {
+            Some(label) => async_write.with_name(&label),
+            None => async_write,
+        })
     }
 }
 
@@ -0,0 +1,124 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures_util::{ready, Sink};
+use wasm_bindgen::prelude::*;
+
+struct Shared<Si> {
+    sink: Option<Si>,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl<Si> Shared<Si> {
+    fn finish(&mut self, sink: Option<Si>) {
+        self.sink = sink;
+        self.done = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`Future`] for the [`from_sink_recoverable`](super::WritableStream::from_sink_recoverable)
+/// method.
+///
+/// This resolves with the original sink once the stream has closed and the sink has been
+/// [closed](https://docs.rs/futures/0.3.30/futures/sink/trait.Sink.html#tymethod.poll_close)
+/// successfully. If the stream is aborted or errors instead, this resolves with `None`.
+///
+/// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+#[must_use = "futures do nothing unless polled"]
+pub struct RecoverSink<Si> {
+    shared: Rc<RefCell<Shared<Si>>>,
+}
+
+impl<Si> core::fmt::Debug for RecoverSink<Si> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RecoverSink").finish_non_exhaustive()
+    }
+}
+
+impl<Si> Future for RecoverSink<Si> {
+    type Output = Option<Si>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.borrow_mut();
+        if shared.done {
+            Poll::Ready(shared.sink.take())
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Wraps a [`Sink`], handing it back to a [`RecoverSink`] after it has been closed.
+pub(super) struct ReturnOnClose<Si> {
+    sink: Option<Si>,
+    shared: Rc<RefCell<Shared<Si>>>,
+}
+
+impl<Si> ReturnOnClose<Si> {
+    pub fn new(sink: Si) -> (Self, RecoverSink<Si>) {
+        let shared = Rc::new(RefCell::new(Shared {
+            sink: None,
+            done: false,
+            waker: None,
+        }));
+        let wrapper = ReturnOnClose {
+            sink: Some(sink),
+            shared: shared.clone(),
+        };
+        (wrapper, RecoverSink { shared })
+    }
+
+    fn sink(&mut self) -> Pin<&mut Si>
+    where
+        Si: Unpin,
+    {
+        // The sink is only taken out after it has been closed,
+        // and the stream never uses it again after that.
+        Pin::new(self.sink.as_mut().unwrap_throw())
+    }
+}
+
+impl<Si> Sink<JsValue> for ReturnOnClose<Si>
+where
+    Si: Sink<JsValue, Error = JsValue> + Unpin,
+{
+    type Error = JsValue;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink().poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), Self::Error> {
+        self.sink().start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.sink().poll_close(cx))?;
+        // Closed successfully, hand the sink back
+        let sink = self.sink.take();
+        self.shared.borrow_mut().finish(sink);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<Si> Drop for ReturnOnClose<Si> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        if !shared.done {
+            // Stream was aborted or errored, the sink cannot be recovered
+            shared.finish(None);
+        }
+    }
+}
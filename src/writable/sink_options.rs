@@ -0,0 +1,101 @@
+use std::fmt;
+use std::future::Future;
+
+use futures_util::{FutureExt, Sink};
+use wasm_bindgen::JsValue;
+
+use super::into_underlying_sink::{AbortFn, IntoUnderlyingSink, IsTransientFn};
+
+/// Options for [`from_sink_with_options`](super::WritableStream::from_sink_with_options).
+///
+/// Every option is disabled by default, so a blank set of options behaves like
+/// [`from_sink`](super::WritableStream::from_sink). Options can be freely combined,
+/// for example to retry writes that fail with a transient error while still bounding
+/// the total time of every write with a timeout.
+#[derive(Default)]
+pub struct SinkOptions {
+    write_timeout: Option<u32>,
+    autoflush: Option<u32>,
+    retry: Option<(u32, Box<IsTransientFn>)>,
+    on_abort: Option<Box<AbortFn>>,
+}
+
+impl SinkOptions {
+    /// Creates a blank new set of sink options.
+    ///
+    /// Equivalent to [`SinkOptions::default`](Default::default).
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets a timeout for every write, see
+    /// [`from_sink_with_write_timeout`](super::WritableStream::from_sink_with_write_timeout).
+    ///
+    /// When combined with [`retry`](Self::retry), the timeout covers all attempts of a write.
+    pub fn write_timeout(mut self, ms: u32) -> Self {
+        self.write_timeout = Some(ms);
+        self
+    }
+
+    /// Flushes the sink periodically instead of after every write, see
+    /// [`from_sink_autoflush`](super::WritableStream::from_sink_autoflush).
+    pub fn autoflush(mut self, interval_ms: u32) -> Self {
+        self.autoflush = Some(interval_ms);
+        self
+    }
+
+    /// Retries writes that fail with a transient error, see
+    /// [`from_sink_with_retry`](super::WritableStream::from_sink_with_retry).
+    pub fn retry<F>(mut self, max_retries: u32, is_transient: F) -> Self
+    where
+        F: Fn(&JsValue) -> bool + 'static,
+    {
+        self.retry = Some((max_retries, Box::new(is_transient)));
+        self
+    }
+
+    /// Sets a handler for when the stream is aborted, see
+    /// [`from_sink_with_abort_handler`](super::WritableStream::from_sink_with_abort_handler).
+    pub fn abort_handler<F, Fut>(mut self, on_abort: F) -> Self
+    where
+        F: FnOnce(JsValue) -> Fut + 'static,
+        Fut: Future<Output = Result<(), JsValue>> + 'static,
+    {
+        self.on_abort = Some(Box::new(move |reason| on_abort(reason).boxed_local()));
+        self
+    }
+
+    pub(super) fn into_underlying_sink(
+        self,
+        sink: Box<dyn Sink<JsValue, Error = JsValue>>,
+    ) -> IntoUnderlyingSink {
+        let mut sink = IntoUnderlyingSink::new(sink);
+        if let Some(ms) = self.write_timeout {
+            sink = sink.with_write_timeout(ms);
+        }
+        if let Some(interval_ms) = self.autoflush {
+            sink = sink.with_autoflush(interval_ms);
+        }
+        if let Some((max_retries, is_transient)) = self.retry {
+            sink = sink.with_retry(max_retries, is_transient);
+        }
+        if let Some(on_abort) = self.on_abort {
+            sink = sink.with_abort_handler(on_abort);
+        }
+        sink
+    }
+}
+
+impl fmt::Debug for SinkOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkOptions")
+            .field("write_timeout", &self.write_timeout)
+            .field("autoflush", &self.autoflush)
+            .field(
+                "max_retries",
+                &self.retry.as_ref().map(|(max_retries, _)| max_retries),
+            )
+            .field("abort_handler", &self.on_abort.is_some())
+            .finish()
+    }
+}
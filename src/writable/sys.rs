@@ -5,6 +5,7 @@ use wasm_bindgen::prelude::*;
 pub use web_sys::WritableStream;
 pub use web_sys::WritableStreamDefaultWriter;
 
+use crate::writable::into_underlying_pipelined_sink::IntoUnderlyingPipelinedSink;
 use crate::writable::into_underlying_sink::IntoUnderlyingSink;
 
 #[wasm_bindgen]
@@ -16,4 +17,9 @@ extern "C" {
 
     #[wasm_bindgen(constructor, js_class = WritableStream)]
     pub(crate) fn new_with_into_underlying_sink(sink: IntoUnderlyingSink) -> WritableStreamExt;
+
+    #[wasm_bindgen(constructor, js_class = WritableStream)]
+    pub(crate) fn new_with_into_underlying_pipelined_sink(
+        sink: IntoUnderlyingPipelinedSink,
+    ) -> WritableStreamExt;
 }
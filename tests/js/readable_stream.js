@@ -47,6 +47,32 @@ export function new_readable_byte_stream_from_array(chunks) {
     });
 }
 
+export function new_errored_readable_byte_stream(error) {
+    return new ReadableStream({
+        type: 'bytes',
+        start(controller) {
+            controller.error(error);
+        }
+    });
+}
+
+export function new_readable_stream_with_cancel_callback(onCancel) {
+    return new ReadableStream({
+        cancel(reason) {
+            onCancel(reason);
+        }
+    });
+}
+
+export function new_readable_byte_stream_with_cancel_callback(onCancel) {
+    return new ReadableStream({
+        type: 'bytes',
+        cancel(reason) {
+            onCancel(reason);
+        }
+    });
+}
+
 export function new_readable_stream_with_rejecting_cancel() {
     return new ReadableStream({
         cancel(reason) {
@@ -64,6 +90,51 @@ export function new_readable_byte_stream_with_rejecting_cancel() {
     });
 }
 
+/**
+ * Creates an object that mimics a `ReadableStream`, whose reader reports the given `value`
+ * together with `done: true` on every read. Streams following the standard never do this,
+ * but custom implementations may.
+ */
+export function new_readable_stream_with_done_value(value) {
+    return {
+        getReader() {
+            return {
+                closed: Promise.resolve(),
+                read() {
+                    return Promise.resolve({value, done: true});
+                },
+                releaseLock() {
+                }
+            };
+        }
+    };
+}
+
+/**
+ * An `EventTarget` which keeps track of how many event listeners are currently attached.
+ */
+class CountingEventTarget extends EventTarget {
+    listenerCount = 0;
+
+    addEventListener(type, listener, options) {
+        this.listenerCount++;
+        super.addEventListener(type, listener, options);
+    }
+
+    removeEventListener(type, listener, options) {
+        this.listenerCount--;
+        super.removeEventListener(type, listener, options);
+    }
+}
+
+export function new_counting_event_target() {
+    return new CountingEventTarget();
+}
+
+export function get_event_listener_count(target) {
+    return target.listenerCount;
+}
+
 /**
  * Tests whether `reader.releaseLock()` is allowed while there are pending read requests.
  *
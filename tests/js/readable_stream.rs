@@ -8,7 +8,17 @@ extern "C" {
     pub fn new_noop_readable_byte_stream() -> sys::ReadableStream;
     pub fn new_readable_stream_from_array(chunks: Box<[JsValue]>) -> sys::ReadableStream;
     pub fn new_readable_byte_stream_from_array(chunks: Box<[JsValue]>) -> sys::ReadableStream;
+    pub fn new_errored_readable_byte_stream(error: &JsValue) -> sys::ReadableStream;
+    pub fn new_readable_stream_with_cancel_callback(
+        on_cancel: &js_sys::Function,
+    ) -> sys::ReadableStream;
+    pub fn new_readable_byte_stream_with_cancel_callback(
+        on_cancel: &js_sys::Function,
+    ) -> sys::ReadableStream;
     pub fn new_readable_stream_with_rejecting_cancel() -> sys::ReadableStream;
     pub fn new_readable_byte_stream_with_rejecting_cancel() -> sys::ReadableStream;
+    pub fn new_readable_stream_with_done_value(value: &JsValue) -> sys::ReadableStream;
+    pub fn new_counting_event_target() -> web_sys::EventTarget;
+    pub fn get_event_listener_count(target: &web_sys::EventTarget) -> u32;
     pub fn supports_release_lock_with_pending_read() -> bool;
 }
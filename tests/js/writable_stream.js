@@ -6,7 +6,7 @@ const TYPE_WRITE = 0;
 const TYPE_CLOSE = 1;
 const TYPE_ABORT = 2;
 
-export function new_recording_writable_stream() {
+export function new_recording_writable_stream(strategy) {
     const events = [];
     const stream = new WritableStream({
         write(chunk) {
@@ -18,6 +18,10 @@ export function new_recording_writable_stream() {
         abort(reason) {
             events.push({type: TYPE_ABORT, reason});
         }
-    });
+    }, strategy);
     return {stream, events};
 }
+
+export function new_recording_byte_writable_stream(highWaterMark) {
+    return new_recording_writable_stream(new ByteLengthQueuingStrategy({highWaterMark}));
+}
@@ -10,6 +10,7 @@ use wasm_streams::writable::*;
 extern "C" {
     pub fn new_noop_writable_stream() -> sys::WritableStream;
     fn new_recording_writable_stream() -> WritableStreamAndEvents;
+    fn new_recording_byte_writable_stream(high_water_mark: f64) -> WritableStreamAndEvents;
 
     #[derive(Clone, Debug)]
     type WritableStreamAndEvents;
@@ -44,6 +45,12 @@ impl RecordingWritableStream {
         }
     }
 
+    pub fn new_with_byte_high_water_mark(high_water_mark: f64) -> Self {
+        Self {
+            raw: new_recording_byte_writable_stream(high_water_mark),
+        }
+    }
+
     pub fn stream(&self) -> sys::WritableStream {
         self.raw.stream()
     }
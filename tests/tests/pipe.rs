@@ -1,4 +1,4 @@
-use futures_util::stream::iter;
+use futures_util::stream::{iter, pending};
 use futures_util::{SinkExt, StreamExt};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
@@ -83,3 +83,115 @@ async fn test_pipe_prevent_close() {
     // Readable stream must be closed
     readable.get_reader().closed().await.unwrap();
 }
+
+#[wasm_bindgen_test]
+async fn test_pipe_spawn() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let (sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let writable = WritableStream::from_sink(sink);
+
+    let handle = readable.spawn_pipe_to(writable);
+    handle.join().await.unwrap();
+
+    // All chunks must be sent to sink
+    let output = stream.collect::<Vec<_>>().await;
+    assert_eq!(output, chunks);
+}
+
+#[wasm_bindgen_test]
+async fn test_pipe_spawn_abort() {
+    let readable = ReadableStream::from_stream(pending());
+
+    let recording_stream = RecordingWritableStream::new();
+    let writable = WritableStream::from_raw(recording_stream.stream());
+
+    let handle = readable.spawn_pipe_to(writable);
+    handle.abort();
+    assert!(handle.join().await.is_err());
+
+    // Destination must be aborted
+    let events = recording_stream.events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], RecordedEvent::Abort(_)));
+}
+
+#[wasm_bindgen_test]
+async fn test_pipe_spawn_to_sink_with_done() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let (sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let (writable, done) = WritableStream::from_sink_with_done(sink);
+
+    let handle = readable.spawn_pipe_to(writable);
+    // The sink must be done once the pipe finishes
+    done.await.unwrap();
+    handle.join().await.unwrap();
+
+    let output = stream.collect::<Vec<_>>().await;
+    assert_eq!(output, chunks);
+
+    // An aborted pipe aborts the sink
+    let readable = ReadableStream::from_stream(pending());
+    let (sink, _stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let (writable, done) = WritableStream::from_sink_with_done(sink);
+
+    let handle = readable.spawn_pipe_to(writable);
+    handle.abort();
+    assert!(done.await.is_err());
+    assert!(handle.join().await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_pipe_drain_into_sink() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let (sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+
+    readable.drain_into(sink).await.unwrap();
+
+    // All chunks must be sent to sink, and the sink must be closed
+    let output = stream.collect::<Vec<_>>().await;
+    assert_eq!(output, chunks);
+}
+
+#[wasm_bindgen_test]
+async fn test_pipe_fanout() {
+    let stream = iter(vec!["Hello", "world!"]).map(|s| Ok(JsValue::from(s)));
+    let readable = ReadableStream::from_stream(stream);
+
+    let first_recording = RecordingWritableStream::new();
+    let second_recording = RecordingWritableStream::new();
+    readable
+        .fanout(vec![
+            WritableStream::from_raw(first_recording.stream()),
+            WritableStream::from_raw(second_recording.stream()),
+        ])
+        .await
+        .unwrap();
+
+    // All chunks must be sent to both sinks
+    for recording_stream in [first_recording, second_recording] {
+        assert_eq!(
+            recording_stream.events(),
+            [
+                RecordedEvent::Write(JsValue::from("Hello")),
+                RecordedEvent::Write(JsValue::from("world!")),
+                RecordedEvent::Close
+            ]
+        );
+    }
+}
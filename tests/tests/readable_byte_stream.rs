@@ -1,11 +1,15 @@
+use std::cell::{Cell, RefCell};
 use std::pin::Pin;
-use std::task::Poll;
+use std::rc::Rc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
-use futures_util::AsyncReadExt;
+use futures_util::stream::iter;
 use futures_util::{poll, FutureExt};
+use futures_util::{AsyncRead, AsyncReadExt, AsyncWriteExt, StreamExt};
 use gloo_timers::future::sleep;
 use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
 
 use wasm_streams::readable::*;
@@ -168,6 +172,21 @@ async fn test_readable_byte_stream_from_async_read() {
     reader.closed().await.unwrap();
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_from_sync_read() {
+    static SYNC_READ: [u8; 6] = [1, 2, 3, 4, 5, 6];
+    let readable = ReadableStream::from_sync_read(std::io::Cursor::new(&SYNC_READ[..]), 2);
+
+    let chunks = readable
+        .into_byte_vec_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+}
+
 #[wasm_bindgen_test]
 async fn test_readable_byte_stream_from_async_read_cancel() {
     static ASYNC_READ: [u8; 6] = [1, 2, 3, 4, 5, 6];
@@ -313,6 +332,25 @@ async fn test_readable_byte_stream_into_async_read_auto_cancel_rejects() {
     sleep(Duration::from_millis(100)).await;
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_drop_cancel_reason() {
+    let reasons = Rc::new(RefCell::new(Vec::new()));
+    let on_cancel = Closure::<dyn FnMut(JsValue)>::new({
+        let reasons = reasons.clone();
+        move |reason| reasons.borrow_mut().push(reason)
+    });
+    let raw_readable =
+        new_readable_byte_stream_with_cancel_callback(on_cancel.as_ref().unchecked_ref());
+    let readable = ReadableStream::from_raw(raw_readable);
+
+    let async_read = readable
+        .into_async_read()
+        .set_drop_cancel_reason(JsValue::from("consumer dropped"));
+    drop(async_read);
+
+    assert_eq!(*reasons.borrow(), vec![JsValue::from("consumer dropped")]);
+}
+
 #[wasm_bindgen_test]
 async fn test_readable_byte_stream_into_async_read_manual_cancel() {
     let raw_readable = new_noop_readable_byte_stream();
@@ -338,3 +376,644 @@ async fn test_readable_byte_stream_into_async_read_manual_cancel() {
     let mut reader = readable.get_reader();
     assert_eq!(reader.read().await.unwrap(), None);
 }
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_from_byte_stream() {
+    let stream = iter(vec![vec![1, 2, 3], vec![4, 5], vec![6]]).map(Ok);
+    let readable = ReadableStream::from_byte_stream(stream);
+
+    let mut async_read = readable.into_async_read();
+    let mut buf = vec![];
+    assert_eq!(async_read.read_to_end(&mut buf).await.unwrap(), 6);
+    assert_eq!(&buf, &[1, 2, 3, 4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_with_name() {
+    let stream = iter(vec![Ok(vec![1, 2, 3]), Err(JsValue::from_str("oops"))]);
+    let readable = ReadableStream::from_byte_stream(stream);
+
+    let mut async_read = readable.into_async_read().with_name("body");
+    let mut buf = vec![];
+    let err = async_read.read_to_end(&mut buf).await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("body: "), "{}", message);
+    assert!(message.contains("oops"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_with_label() {
+    let stream = iter(vec![Ok(vec![1, 2, 3]), Err(JsValue::from_str("oops"))]);
+    let readable = ReadableStream::from_byte_stream(stream).with_label("body");
+    let debug = format!("{:?}", readable);
+    assert!(debug.contains("body"), "{}", debug);
+
+    let mut async_read = readable.into_async_read();
+    let mut buf = vec![];
+    let err = async_read.read_to_end(&mut buf).await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("body: "), "{}", message);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_last_js_error() {
+    let error = JsValue::from(js_sys::TypeError::new("oops"));
+    let readable = ReadableStream::from_raw(new_errored_readable_byte_stream(&error));
+
+    let mut async_read = readable.into_async_read();
+    assert!(async_read.last_js_error().is_none());
+    let mut buf = vec![];
+    let err = async_read.read_to_end(&mut buf).await.unwrap_err();
+    assert!(err.to_string().contains("oops"), "{}", err);
+
+    // Must return the original error object
+    assert_eq!(async_read.last_js_error(), Some(&error));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_from_async_read_with_signal() {
+    let (async_read, mut async_write) = ByteChannel::new().split();
+    let (async_read, observer) = observe_drop(async_read);
+    let controller = web_sys::AbortController::new().unwrap();
+    let mut readable =
+        ReadableStream::from_async_read_with_signal(async_read, 2, controller.signal());
+    let mut reader = readable.get_byob_reader();
+
+    async_write.write_all(&[1, 2]).await.unwrap();
+    let mut dst = [0u8; 2];
+    assert_eq!(reader.read(&mut dst).await.unwrap(), 2);
+    assert_eq!(&dst, &[1, 2]);
+
+    // Start reading
+    // Since no more bytes are written, this read remains pending until the signal is aborted
+    let mut fut = reader.read(&mut dst).boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    controller.abort_with_reason(&JsValue::from_str("aborted"));
+    assert_eq!(fut.await, Err(JsValue::from_str("aborted")));
+
+    // The AsyncRead must be dropped once the aborted pull completes
+    sleep(Duration::from_millis(0)).await;
+    assert!(observer.is_dropped());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_byte_stream() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let mut async_read = readable.into_byte_stream().into_async_read();
+    let mut output = Vec::new();
+    async_read.read_to_end(&mut output).await.unwrap();
+    assert_eq!(output, [1, 2, 3, 4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_byob_reader_forget() {
+    let mut readable = ReadableStream::from_raw(new_noop_readable_byte_stream());
+
+    // Forgetting the reader must keep the stream locked
+    let raw_reader = readable.get_byob_reader().forget();
+    assert!(readable.is_locked());
+
+    raw_reader.release_lock();
+    assert!(!readable.is_locked());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_byte_vec_stream() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let output = readable
+        .into_byte_vec_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(output, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_byte_vec_stream_invalid_chunk() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+
+    let mut stream = Box::pin(readable.into_byte_vec_stream());
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(error.is_instance_of::<js_sys::TypeError>());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_rate_limit_bytes() {
+    let data = (0..=255u8).cycle().take(1000).collect::<Vec<_>>();
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&data[0..700]).into(),
+            Uint8Array::from(&data[700..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    // At 2000 bytes per second, 1000 bytes must take about 500 milliseconds
+    let start = js_sys::Date::now();
+    let output = readable
+        .rate_limit_bytes(2000)
+        .into_byte_vec_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let elapsed = js_sys::Date::now() - start;
+
+    // Chunks are split into slices of at most 200 bytes
+    assert!(output.iter().all(|slice| slice.len() <= 200));
+    assert_eq!(output.concat(), data);
+    assert!(elapsed >= 400.0, "{}", elapsed);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_with_checksum() {
+    // 64-bit FNV-1a
+    struct Fnv(u64);
+    impl std::hash::Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+    let (readable, handle) = readable.with_checksum(Fnv(0xcbf29ce484222325));
+    assert_eq!(handle.digest(), None);
+
+    let mut reader = readable.into_stream();
+    assert!(reader.next().await.is_some());
+    assert_eq!(handle.digest(), None);
+    assert!(reader.next().await.is_some());
+    assert_eq!(reader.next().await, None);
+    assert_eq!(handle.digest(), Some(0x9746a713f3a6584a));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_with_memory_budget() {
+    // An endless producer that always has the next chunk ready
+    let produced = Rc::new(Cell::new(0));
+    let stream = iter(std::iter::repeat_with({
+        let produced = produced.clone();
+        move || {
+            produced.set(produced.get() + 4);
+            Ok(Uint8Array::from(&[0u8; 4][..]).into())
+        }
+    }));
+    let readable = ReadableStream::from_stream(stream).with_memory_budget(8);
+    let mut stream = readable.into_stream();
+
+    // While the consumer is paused, only the budget is prefetched
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(produced.get(), 8);
+
+    let mut consumed = 0;
+    for _ in 0..3 {
+        let chunk = stream.next().await.unwrap().unwrap();
+        consumed += chunk.unchecked_into::<Uint8Array>().length();
+        sleep(Duration::from_millis(10)).await;
+        assert_eq!(produced.get() - consumed, 8);
+    }
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_with_memory_budget_splits_chunks() {
+    // An endless producer whose chunk size does not divide the budget
+    let produced = Rc::new(Cell::new(0u32));
+    let stream = iter(std::iter::repeat_with({
+        let produced = produced.clone();
+        move || {
+            let start = produced.get() as u8;
+            produced.set(produced.get() + 3);
+            Ok(Uint8Array::from(&[start, start + 1, start + 2][..]).into())
+        }
+    }));
+    let readable = ReadableStream::from_stream(stream).with_memory_budget(8);
+    let mut stream = readable.into_stream();
+
+    // While the consumer is paused, the third chunk is split to fill the budget exactly
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(produced.get(), 9);
+
+    let mut lengths = Vec::new();
+    let mut output = Vec::new();
+    while output.len() < 20 {
+        let chunk = stream.next().await.unwrap().unwrap();
+        let chunk = chunk.unchecked_into::<Uint8Array>();
+        lengths.push(chunk.length());
+        output.extend(chunk.to_vec());
+        sleep(Duration::from_millis(10)).await;
+        // The queue holds at most the budget,
+        // plus the remaining bytes of a split chunk that are still held back
+        let queued = produced.get() - output.len() as u32;
+        assert!(queued <= 8 + 2, "{} bytes buffered", queued);
+    }
+
+    // The bytes must be delivered in order, regardless of the chunk boundaries
+    assert_eq!(output, (0..output.len() as u8).collect::<Vec<_>>());
+    assert_eq!(&lengths[..4], &[3, 3, 2, 1]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_with_progress() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let progress = Rc::new(RefCell::new(Vec::new()));
+    let readable = readable.with_progress(4, {
+        let progress = progress.clone();
+        move |fraction| progress.borrow_mut().push(fraction)
+    });
+
+    let mut reader = readable.into_stream();
+    assert!(reader.next().await.is_some());
+    assert_eq!(*progress.borrow(), vec![0.75]);
+    assert!(reader.next().await.is_some());
+    assert_eq!(reader.next().await, None);
+    assert_eq!(*progress.borrow(), vec![0.75, 1.0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_collect_bytes_capped() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let bytes = readable.collect_bytes_capped(6).await.unwrap();
+    assert_eq!(bytes, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_collect_bytes_capped_exceeded() {
+    let stream = iter(vec![
+        Ok(Uint8Array::from(&[1, 2, 3][..]).into()),
+        Ok(Uint8Array::from(&[4, 5, 6][..]).into()),
+    ]);
+    let (stream, observer) = observe_drop(stream);
+    let readable = ReadableStream::from_stream(stream);
+
+    let error = readable.collect_bytes_capped(4).await.unwrap_err();
+    assert!(error.is_instance_of::<js_sys::RangeError>());
+    // The stream must be canceled
+    assert!(observer.is_dropped());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_length_prefixed_frames() {
+    // Two frames with a 4-byte big-endian prefix, split at arbitrary chunk boundaries
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[0, 0][..]).into(),
+            Uint8Array::from(&[0, 3, 1, 2][..]).into(),
+            Uint8Array::from(&[3, 0, 0, 0, 2, 4][..]).into(),
+            Uint8Array::from(&[5][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let frames = readable
+        .into_length_prefixed_frames(4, true)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(frames, vec![vec![1, 2, 3], vec![4, 5]]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_length_prefixed_frames_truncated() {
+    // A frame with a 2-byte little-endian prefix, followed by a truncated frame
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![Uint8Array::from(&[1, 0, 1, 3, 0, 2][..]).into()].into_boxed_slice(),
+    ));
+
+    let mut frames = Box::pin(readable.into_length_prefixed_frames(2, false));
+    assert_eq!(frames.next().await.unwrap().unwrap(), vec![1]);
+    assert!(frames.next().await.unwrap().is_err());
+    assert!(frames.next().await.is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_blob_bytes_stream() {
+    let new_blob = |bytes: &[u8]| -> JsValue {
+        let parts = js_sys::Array::of1(&Uint8Array::from(bytes));
+        web_sys::Blob::new_with_u8_array_sequence(&parts)
+            .unwrap()
+            .into()
+    };
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![new_blob(&[1, 2, 3]), new_blob(&[]), new_blob(&[4, 5])].into_boxed_slice(),
+    ));
+
+    let output = readable
+        .into_blob_bytes_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(output, vec![vec![1, 2, 3], vec![], vec![4, 5]]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_blob_bytes_stream_invalid_chunk() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![Uint8Array::from(&[1, 2, 3][..]).into()].into_boxed_slice(),
+    ));
+
+    let mut stream = Box::pin(readable.into_blob_bytes_stream());
+    let error = stream.next().await.unwrap().unwrap_err();
+    assert!(error.is_instance_of::<js_sys::TypeError>());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_with_min_read_size() {
+    struct CountingRead {
+        inner: &'static [u8],
+        reads: Rc<Cell<usize>>,
+    }
+
+    impl AsyncRead for CountingRead {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.reads.set(self.reads.get() + 1);
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    static ASYNC_READ: [u8; 6] = [1, 2, 3, 4, 5, 6];
+    let reads = Rc::new(Cell::new(0));
+    let readable = ReadableStream::from_async_read(
+        CountingRead {
+            inner: &ASYNC_READ[..],
+            reads: reads.clone(),
+        },
+        2,
+    );
+
+    let mut async_read = readable.into_async_read().with_min_read_size(4096);
+    let mut output = Vec::new();
+    let mut buf = [0u8; 1];
+    loop {
+        match async_read.read(&mut buf).await.unwrap() {
+            0 => break,
+            n => output.extend_from_slice(&buf[..n]),
+        }
+    }
+    assert_eq!(output, ASYNC_READ);
+    // One read for all bytes, and one read for the end of the stream
+    assert_eq!(reads.get(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_buffered_len() {
+    let readable = ReadableStream::from_raw(new_readable_byte_stream_from_array(
+        vec![Uint8Array::from(&[1, 2, 3, 4][..]).into()].into_boxed_slice(),
+    ));
+
+    let mut async_read = readable.into_async_read().with_min_read_size(4);
+    assert_eq!(async_read.buffered_len(), 0);
+
+    let mut buf = [0u8; 1];
+    assert_eq!(async_read.read(&mut buf).await.unwrap(), 1);
+    assert_eq!(&buf, &[1]);
+    assert_eq!(async_read.buffered_len(), 3);
+
+    let mut buf = [0u8; 2];
+    assert_eq!(async_read.read(&mut buf).await.unwrap(), 2);
+    assert_eq!(&buf, &[2, 3]);
+    assert_eq!(async_read.buffered_len(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_read_view() {
+    let mut readable = ReadableStream::from_raw(new_readable_byte_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let mut reader = readable.get_byob_reader();
+    let (view, done) = reader.read_view(2).await.unwrap();
+    assert_eq!(view.to_vec(), vec![1, 2]);
+    assert!(!done);
+    let (view, done) = reader.read_view(8).await.unwrap();
+    assert_eq!(view.to_vec(), vec![3, 4, 5, 6]);
+    assert!(!done);
+    let (view, done) = reader.read_view(8).await.unwrap();
+    assert_eq!(view.byte_length(), 0);
+    assert!(done);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_from_async_read_memory_growth() {
+    struct GrowingRead {
+        inner: &'static [u8],
+    }
+
+    impl AsyncRead for GrowingRead {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            // Allocate a large block to (most likely) grow the WebAssembly memory mid-pull
+            let block = vec![0xffu8; 16 * 1024 * 1024];
+            std::hint::black_box(&block);
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    static ASYNC_READ: [u8; 6] = [1, 2, 3, 4, 5, 6];
+    let mut readable = ReadableStream::from_async_read(
+        GrowingRead {
+            inner: &ASYNC_READ[..],
+        },
+        2,
+    );
+
+    let mut reader = readable.get_byob_reader();
+    let mut dst = [0u8; 4];
+    assert_eq!(reader.read(&mut dst).await.unwrap(), 4);
+    assert_eq!(&dst, &[1, 2, 3, 4]);
+    assert_eq!(reader.read(&mut dst).await.unwrap(), 2);
+    assert_eq!(&dst[..2], &[5, 6]);
+    assert_eq!(reader.read(&mut dst).await.unwrap(), 0);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_async_read_with_prefetch() {
+    let readable = ReadableStream::from_raw(new_readable_byte_stream_from_array(
+        vec![Uint8Array::from(&[1, 2, 3, 4][..]).into()].into_boxed_slice(),
+    ));
+    let mut async_read = readable.into_async_read().with_prefetch(8);
+
+    // Give the prefetched read some time to complete
+    sleep(Duration::from_millis(10)).await;
+
+    // First read must complete immediately
+    let mut buf = [0u8; 2];
+    let poll_result = poll!(async_read.read(&mut buf));
+    assert!(matches!(poll_result, Poll::Ready(Ok(2))));
+    assert_eq!(&buf, &[1, 2]);
+    assert_eq!(async_read.read(&mut buf).await.unwrap(), 2);
+    assert_eq!(&buf, &[3, 4]);
+    assert_eq!(async_read.read(&mut buf).await.unwrap(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_into_measured_stream() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5][..]).into(),
+            JsValue::from("Hello"),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let sizes = readable
+        .into_measured_stream()
+        .map(|result| result.unwrap().1)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(sizes, vec![3, 2, 0]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_tap_bytes() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let observed = Rc::new(RefCell::new(Vec::new()));
+    let readable = readable.tap_bytes({
+        let observed = observed.clone();
+        move |bytes| observed.borrow_mut().extend_from_slice(bytes)
+    });
+
+    let output = readable
+        .into_byte_vec_stream()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(output, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    assert_eq!(*observed.borrow(), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_from_async_read_seek() {
+    static DATA: [u8; 6] = [1, 2, 3, 4, 5, 6];
+
+    let (mut readable, token) =
+        ReadableStream::from_async_read_seek(futures_util::io::Cursor::new(&DATA[..]), 2, 0);
+    let mut reader = readable.get_byob_reader();
+    let mut dst = [0u8; 3];
+    assert_eq!(reader.read(&mut dst).await.unwrap(), 3);
+    assert_eq!(&dst, &[1, 2, 3]);
+    reader.cancel().await.unwrap();
+    assert_eq!(token.offset(), 3);
+
+    // Resume from the recorded offset
+    let (readable, token) = ReadableStream::from_async_read_seek(
+        futures_util::io::Cursor::new(&DATA[..]),
+        2,
+        token.offset(),
+    );
+    let mut output = Vec::new();
+    readable
+        .into_async_read()
+        .read_to_end(&mut output)
+        .await
+        .unwrap();
+    assert_eq!(output, vec![4, 5, 6]);
+    assert_eq!(token.offset(), 6);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_copy_to_async_write() {
+    let readable = ReadableStream::from_raw(new_readable_byte_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+    let (mut async_read, mut async_write) = ByteChannel::new().split();
+
+    let copied = readable
+        .copy_to_async_write(&mut async_write)
+        .await
+        .unwrap();
+    assert_eq!(copied, 6);
+    async_write.close().await.unwrap();
+
+    let mut output = Vec::new();
+    async_read.read_to_end(&mut output).await.unwrap();
+    assert_eq!(output, vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_byte_stream_byob_reader_read_empty() {
+    let mut readable = ReadableStream::from_raw(new_noop_readable_byte_stream());
+    let mut reader = readable.get_byob_reader();
+
+    // Must complete right away, without starting a read on the stream
+    let poll_result = poll!(reader.read(&mut []).boxed_local());
+    assert!(matches!(poll_result, Poll::Ready(Ok(0))));
+
+    // No read is pending, so the lock can always be released
+    reader.try_release_lock().unwrap();
+    assert!(!readable.is_locked());
+}
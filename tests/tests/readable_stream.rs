@@ -1,9 +1,12 @@
+use std::cell::{Cell, RefCell};
 use std::pin::Pin;
-use std::task::Poll;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
 use std::time::Duration;
 
-use futures_util::stream::{iter, pending, StreamExt, TryStreamExt};
-use futures_util::{poll, AsyncReadExt, FutureExt};
+use futures_util::future::join;
+use futures_util::stream::{iter, pending, poll_fn, FusedStream, StreamExt, TryStreamExt};
+use futures_util::{poll, AsyncReadExt, FutureExt, SinkExt};
 use gloo_timers::future::sleep;
 use js_sys::Uint8Array;
 use wasm_bindgen::prelude::*;
@@ -43,6 +46,29 @@ async fn test_readable_stream_into_stream() {
     assert_eq!(stream.next().await, None);
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_chunk_kind() {
+    let chunk = JsValue::from(Uint8Array::from(&[1, 2, 3][..]));
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![chunk.clone()].into_boxed_slice(),
+    ));
+    let mut stream = readable.into_stream();
+
+    assert_eq!(stream.chunk_kind().await, Some(ChunkKind::Bytes));
+    // The inspected chunk must not be lost
+    assert_eq!(stream.next().await, Some(Ok(chunk)));
+    assert_eq!(stream.chunk_kind().await, None);
+    assert_eq!(stream.next().await, None);
+
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+    assert_eq!(
+        readable.into_stream().chunk_kind().await,
+        Some(ChunkKind::Text)
+    );
+}
+
 #[wasm_bindgen_test]
 fn test_readable_stream_into_stream_impl_unpin() {
     let readable = ReadableStream::from_raw(new_noop_readable_stream());
@@ -123,6 +149,21 @@ async fn test_readable_stream_multiple_readers() {
     assert!(!readable.is_locked());
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_stream_reader_forget() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+
+    // Forgetting the reader must keep the stream locked
+    let raw_reader = readable.get_reader().forget();
+    assert!(readable.is_locked());
+
+    // The raw reader must still be usable
+    raw_reader.release_lock();
+    assert!(!readable.is_locked());
+}
+
 #[wasm_bindgen_test]
 async fn test_readable_stream_abort_read() {
     if supports_release_lock_with_pending_read() {
@@ -224,6 +265,158 @@ async fn test_readable_stream_tee() {
     assert_eq!(right_chunks, chunks);
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_stream_tee_with_backpressure() {
+    let chunks = (0..3).map(JsValue::from).collect::<Vec<_>>();
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let (mut fast, mut slow) = readable.tee_with_backpressure(1);
+    let mut fast_reader = fast.get_reader();
+    let mut slow_reader = slow.get_reader();
+
+    assert_eq!(fast_reader.read().await.unwrap(), Some(JsValue::from(0)));
+
+    // The slow branch has not read its chunk yet, so the fast branch must wait
+    let mut fut = fast_reader.read().boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    assert_eq!(slow_reader.read().await.unwrap(), Some(JsValue::from(0)));
+    assert_eq!(fut.await.unwrap(), Some(JsValue::from(1)));
+
+    let fast_chunks = fast_reader.into_stream().try_collect::<Vec<_>>();
+    let slow_chunks = slow_reader.into_stream().try_collect::<Vec<_>>();
+    let (fast_chunks, slow_chunks) = join(fast_chunks, slow_chunks).await;
+    assert_eq!(fast_chunks.unwrap(), chunks[2..]);
+    assert_eq!(slow_chunks.unwrap(), chunks[1..]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_terminated_after_close() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+    let mut stream = readable.into_stream();
+    assert!(!stream.is_terminated());
+
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("Hello"))));
+    assert!(!stream.is_terminated());
+    assert_eq!(stream.next().await, None);
+    assert!(stream.is_terminated());
+
+    // Polling after termination must keep returning None
+    assert_eq!(stream.next().await, None);
+    assert!(stream.is_terminated());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_terminated_after_error() {
+    let error = JsValue::from_str("error");
+    let readable =
+        ReadableStream::from_stream(iter(vec![Ok(JsValue::from("Hello")), Err(error.clone())]));
+    let mut stream = readable.into_stream();
+
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("Hello"))));
+    assert_eq!(stream.next().await, Some(Err(error)));
+    assert!(stream.is_terminated());
+
+    // Polling after termination must keep returning None
+    assert_eq!(stream.next().await, None);
+    assert!(stream.is_terminated());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_timed() {
+    let readable = ReadableStream::from_stream(
+        iter(vec![JsValue::from("Hello"), JsValue::from("world!")])
+            .then(|chunk| async move {
+                sleep(Duration::from_millis(20)).await;
+                Ok(chunk)
+            })
+            .boxed_local(),
+    );
+    let (mut stream, latency) = readable.into_stream_timed();
+    assert_eq!(latency.get(), None);
+
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("Hello"))));
+    let first_latency = latency.get().unwrap();
+    assert!(first_latency > 0.0, "{}", first_latency);
+
+    // Later chunks must not change the latency
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("world!"))));
+    assert_eq!(latency.get(), Some(first_latency));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_try_map() {
+    let error = JsValue::from_str("error");
+    let readable = ReadableStream::from_stream(iter(vec![
+        Ok(JsValue::from(1)),
+        Ok(JsValue::from("two")),
+        Ok(JsValue::from(3)),
+        Err(error.clone()),
+    ]));
+    let mut stream =
+        Box::pin(readable.into_stream_try_map(|chunk| {
+            chunk.as_f64().ok_or_else(|| JsValue::from("not a number"))
+        }));
+
+    assert_eq!(stream.next().await, Some(Ok(1.0)));
+    assert_eq!(
+        stream.next().await,
+        Some(Err(JsValue::from("not a number")))
+    );
+    assert_eq!(stream.next().await, Some(Ok(3.0)));
+    assert_eq!(stream.next().await, Some(Err(error)));
+    assert_eq!(stream.next().await, None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_with_error_meta() {
+    let error = js_sys::Object::new();
+    js_sys::Reflect::set(&error, &"retryAfter".into(), &500.into()).unwrap();
+    let readable = ReadableStream::from_stream(iter(vec![
+        Ok(JsValue::from("Hello")),
+        Err(error.clone().into()),
+    ]));
+    let mut stream = Box::pin(readable.into_stream_with_error_meta());
+
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("Hello"))));
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(err.value, JsValue::from(error));
+    assert_eq!(err.retry_after_ms, Some(500));
+    assert_eq!(stream.next().await, None);
+
+    let readable = ReadableStream::error(JsValue::from("error"));
+    let mut stream = Box::pin(readable.into_stream_with_error_meta());
+    let err = stream.next().await.unwrap().unwrap_err();
+    assert_eq!(err.value, JsValue::from("error"));
+    assert_eq!(err.retry_after_ms, None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_into_readable_stream() {
+    let raw = new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    );
+    let stream = ReadableStream::from_raw(raw.clone()).into_stream();
+
+    // An unpolled stream gives back the original stream
+    let readable = stream.into_readable_stream();
+    assert_eq!(JsValue::from(readable.as_raw()), JsValue::from(&raw));
+    assert!(!readable.is_locked());
+
+    // A stream with an inspected chunk is wrapped, without losing that chunk
+    let mut stream = readable.into_stream();
+    assert_eq!(stream.chunk_kind().await, Some(ChunkKind::Text));
+    let readable = stream.into_readable_stream();
+    assert_ne!(JsValue::from(readable.as_raw()), JsValue::from(&raw));
+    let mut stream = readable.into_stream();
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("Hello"))));
+    assert_eq!(stream.next().await, Some(Ok(JsValue::from("world!"))));
+    assert_eq!(stream.next().await, None);
+}
+
 #[wasm_bindgen_test]
 async fn test_readable_stream_into_stream_auto_cancel() {
     let raw_readable = new_noop_readable_stream();
@@ -249,6 +442,24 @@ async fn test_readable_stream_into_stream_auto_cancel() {
     assert_eq!(reader.read().await.unwrap(), None);
 }
 
+#[wasm_bindgen_test]
+async fn test_readable_stream_into_stream_drop_cancel_reason() {
+    let reasons = Rc::new(RefCell::new(Vec::new()));
+    let on_cancel = Closure::<dyn FnMut(JsValue)>::new({
+        let reasons = reasons.clone();
+        move |reason| reasons.borrow_mut().push(reason)
+    });
+    let raw_readable = new_readable_stream_with_cancel_callback(on_cancel.as_ref().unchecked_ref());
+    let readable = ReadableStream::from_raw(raw_readable);
+
+    let stream = readable
+        .into_stream()
+        .set_drop_cancel_reason(JsValue::from("consumer dropped"));
+    drop(stream);
+
+    assert_eq!(*reasons.borrow(), vec![JsValue::from("consumer dropped")]);
+}
+
 #[wasm_bindgen_test]
 async fn test_readable_stream_into_stream_manual_cancel() {
     let raw_readable = new_noop_readable_stream();
@@ -346,3 +557,1019 @@ async fn test_readable_stream_from_js_array() {
     assert_eq!(reader.read().await.unwrap(), None);
     reader.closed().await.unwrap();
 }
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_read_timeout() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let mut readable = ReadableStream::from_stream(stream.map(Ok));
+    let mut reader = readable.get_reader();
+
+    // No chunk is available yet, so the read must time out
+    assert_eq!(reader.read_timeout(10).await, Err(None));
+
+    // The timed out read must not be lost
+    sink.send(JsValue::from("Hello")).await.unwrap();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+
+    sink.send(JsValue::from("world!")).await.unwrap();
+    assert_eq!(
+        reader.read_timeout(1000).await,
+        Ok(Some(JsValue::from("world!")))
+    );
+
+    sink.close().await.unwrap();
+    assert_eq!(reader.read_timeout(1000).await, Ok(None));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_repeat() {
+    let readable = ReadableStream::repeat(JsValue::from("Hello"), 3);
+    let chunks = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        chunks,
+        vec![
+            JsValue::from("Hello"),
+            JsValue::from("Hello"),
+            JsValue::from("Hello")
+        ]
+    );
+
+    let readable = ReadableStream::repeat(JsValue::from("Hello"), 0);
+    let chunks = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(chunks, Vec::<JsValue>::new());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_cycle() {
+    let values = vec![JsValue::from(1), JsValue::from(2)];
+    let readable = ReadableStream::cycle(values, 5);
+    let chunks = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        chunks,
+        vec![
+            JsValue::from(1),
+            JsValue::from(2),
+            JsValue::from(1),
+            JsValue::from(2),
+            JsValue::from(1)
+        ]
+    );
+
+    let readable = ReadableStream::cycle(vec![], 5);
+    let chunks = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(chunks, Vec::<JsValue>::new());
+}
+
+#[cfg(all(feature = "panic-capture", panic = "unwind"))]
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_panic_capture() {
+    let stream = iter(vec![Ok(JsValue::from("Hello"))])
+        .chain(futures_util::stream::once(async { panic!("oh no") }));
+    let mut readable = ReadableStream::from_stream(stream);
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    // The panic must error the stream
+    let err = reader.read().await.unwrap_err();
+    let err = err.dyn_into::<js_sys::Error>().unwrap();
+    assert!(err.message().as_string().unwrap().contains("oh no"));
+}
+
+#[cfg(not(all(feature = "panic-capture", panic = "unwind")))]
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_without_panic_capture() {
+    // Without panic capture, chunks and errors must pass through unchanged
+    let stream = iter(vec![Ok(JsValue::from("Hello")), Err(JsValue::from("oops"))]);
+    let mut readable = ReadableStream::from_stream(stream);
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    assert_eq!(reader.read().await, Err(JsValue::from("oops")));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_scoped() {
+    let words = vec![String::from("Hello"), String::from("world!")];
+    // This stream borrows from `words`, so it is not 'static
+    let stream = iter(&words).map(|word| Ok(JsValue::from(word.as_str())));
+    let (readable, scope) = ReadableStream::from_stream_scoped(stream);
+
+    let ((), chunks) = join(scope, readable.into_stream().try_collect::<Vec<_>>()).await;
+    assert_eq!(
+        chunks.unwrap(),
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_scoped_drop_scope() {
+    let (mut readable, scope) = ReadableStream::from_stream_scoped(pending());
+    drop(scope);
+
+    // Dropping the scope must error the stream
+    let mut reader = readable.get_reader();
+    assert!(reader.read().await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_tap() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let count = Rc::new(Cell::new(0));
+    let readable = readable.tap({
+        let count = count.clone();
+        move |_chunk| count.set(count.get() + 1)
+    });
+
+    let output = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(output, chunks);
+    assert_eq!(count.get(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_chunks_timeout_count() {
+    let chunks = vec![
+        JsValue::from(1),
+        JsValue::from(2),
+        JsValue::from(3),
+        JsValue::from(4),
+        JsValue::from(5),
+    ];
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(chunks.into_boxed_slice()));
+
+    let output = readable
+        .chunks_timeout(2, 60_000)
+        .into_stream()
+        .map_ok(|group| group.unchecked_into::<js_sys::Array>().to_vec())
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![
+            vec![JsValue::from(1), JsValue::from(2)],
+            vec![JsValue::from(3), JsValue::from(4)],
+            vec![JsValue::from(5)],
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_chunks_timeout_time() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let readable = ReadableStream::from_stream(stream.map(Ok));
+    let mut output = readable
+        .chunks_timeout(10, 20)
+        .into_stream()
+        .map_ok(|group| group.unchecked_into::<js_sys::Array>().to_vec());
+
+    // Only one chunk is available, so the group is flushed after the timeout
+    sink.send(JsValue::from("Hello")).await.unwrap();
+    assert_eq!(
+        output.next().await.unwrap().unwrap(),
+        vec![JsValue::from("Hello")]
+    );
+
+    sink.close().await.unwrap();
+    assert!(output.next().await.is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_message_port() {
+    let channel = web_sys::MessageChannel::new().unwrap();
+    let sender = channel.port1();
+    let mut readable = ReadableStream::from_message_port(channel.port2());
+
+    // Messages posted before the first read must be buffered
+    sender.post_message(&JsValue::from("Hello")).unwrap();
+    sender.post_message(&JsValue::from("world!")).unwrap();
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("world!")));
+    reader.cancel().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_on_done() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+
+    let calls = Rc::new(Cell::new(0));
+    let readable = readable.on_done({
+        let calls = calls.clone();
+        move |error| {
+            assert!(error.is_none());
+            calls.set(calls.get() + 1)
+        }
+    });
+
+    let mut reader = readable.into_stream();
+    assert_eq!(reader.next().await, Some(Ok(JsValue::from("Hello"))));
+    assert_eq!(reader.next().await, Some(Ok(JsValue::from("world!"))));
+    assert_eq!(calls.get(), 0);
+    assert_eq!(reader.next().await, None);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(reader.next().await, None);
+    assert_eq!(calls.get(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_prepend() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ));
+
+    let output = readable
+        .prepend(vec![JsValue::from("header")])
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![
+            JsValue::from("header"),
+            JsValue::from("Hello"),
+            JsValue::from("world!")
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_race() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let immediate = ReadableStream::from_raw(new_readable_stream_from_array(
+        chunks.clone().into_boxed_slice(),
+    ));
+    let raw_pending = new_noop_readable_stream();
+    let pending = ReadableStream::from_raw(raw_pending.clone());
+
+    let output = ReadableStream::race(pending, immediate)
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(output, chunks);
+
+    // The losing stream must be unlocked and cancelled
+    let mut pending = ReadableStream::from_raw(raw_pending);
+    assert!(!pending.is_locked());
+    let mut reader = pending.get_reader();
+    assert_eq!(reader.read().await.unwrap(), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_read_full() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+    let mut reader = readable.get_reader();
+
+    assert_eq!(
+        reader.read_full().await.unwrap(),
+        (Some(JsValue::from("Hello")), false)
+    );
+    assert_eq!(reader.read_full().await.unwrap(), (None, true));
+
+    // A custom reader may report a value along with done
+    let mut readable =
+        ReadableStream::from_raw(new_readable_stream_with_done_value(&JsValue::from("bye")));
+    let mut reader = readable.get_reader();
+    assert_eq!(
+        reader.read_full().await.unwrap(),
+        (Some(JsValue::from("bye")), true)
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_debounce() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let readable = ReadableStream::from_stream(stream.map(Ok));
+    let mut output = readable.debounce(20).into_stream();
+
+    // Only the last chunk of a burst is emitted
+    sink.send(JsValue::from(1)).await.unwrap();
+    sink.send(JsValue::from(2)).await.unwrap();
+    sink.send(JsValue::from(3)).await.unwrap();
+    assert_eq!(output.next().await, Some(Ok(JsValue::from(3))));
+
+    sink.send(JsValue::from(4)).await.unwrap();
+    assert_eq!(output.next().await, Some(Ok(JsValue::from(4))));
+
+    sink.close().await.unwrap();
+    assert_eq!(output.next().await, None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_step_by() {
+    let chunks = (0..5).map(JsValue::from).collect::<Vec<_>>();
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(chunks.into_boxed_slice()));
+
+    let output = readable
+        .step_by(2)
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![JsValue::from(0), JsValue::from(2), JsValue::from(4)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_take_while() {
+    let stream = iter((0..5).map(|i| Ok(JsValue::from(i))));
+    let (stream, observer) = observe_drop(stream);
+    let readable = ReadableStream::from_stream(stream);
+
+    let output = readable
+        .take_while(|chunk| chunk.as_f64().unwrap() < 3.0)
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![JsValue::from(0), JsValue::from(1), JsValue::from(2)]
+    );
+    // The source must be canceled
+    assert!(observer.is_dropped());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_skip_while() {
+    let chunks = [0, 1, 2, 0, 1].map(JsValue::from).to_vec();
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(chunks.into_boxed_slice()));
+
+    let output = readable
+        .skip_while(|chunk| chunk.as_f64().unwrap() < 2.0)
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![JsValue::from(2), JsValue::from(0), JsValue::from(1)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_pausable() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ));
+    let (mut readable, handle) = readable.pausable();
+    let mut reader = readable.get_reader();
+
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+
+    // Reads must stay pending while paused
+    handle.pause();
+    assert!(handle.is_paused());
+    let mut fut = reader.read().boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    sleep(Duration::from_millis(10)).await;
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+
+    handle.resume();
+    assert_eq!(fut.await.unwrap(), Some(JsValue::from("world!")));
+    assert_eq!(reader.read().await.unwrap(), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_error() {
+    let error = JsValue::from_str("oops");
+    let mut readable = ReadableStream::error(error.clone());
+    let mut reader = readable.get_reader();
+
+    assert_eq!(reader.read().await, Err(error.clone()));
+    assert_eq!(reader.read().await, Err(error.clone()));
+    assert_eq!(reader.closed().await, Err(error));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_closable() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let (mut readable, handle) = ReadableStream::from_stream_closable(stream.map(Ok));
+    let mut reader = readable.get_reader();
+
+    sink.send(JsValue::from("Hello")).await.unwrap();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+
+    // Closing must end a pending read, even though the Rust stream has not ended
+    let mut fut = reader.read().boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    handle.close();
+    assert_eq!(fut.await.unwrap(), None);
+    assert_eq!(reader.read().await.unwrap(), None);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_group_by() {
+    let readable = ReadableStream::from_stream(iter((1..=6).map(|i| Ok(JsValue::from(i)))));
+    let mut groups = Box::pin(readable.group_by(|chunk| chunk.as_f64().unwrap() as u32 % 2));
+
+    let (odd_key, odd) = groups.next().await.unwrap();
+    assert_eq!(odd_key, 1);
+    let (even_key, even) = groups.next().await.unwrap();
+    assert_eq!(even_key, 0);
+    assert!(groups.next().await.is_none());
+
+    let odd = odd.into_stream().try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(
+        odd,
+        vec![JsValue::from(1), JsValue::from(3), JsValue::from(5)]
+    );
+    let even = even.into_stream().try_collect::<Vec<_>>().await.unwrap();
+    assert_eq!(
+        even,
+        vec![JsValue::from(2), JsValue::from(4), JsValue::from(6)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_group_by_drop_pending_group() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let readable = ReadableStream::from_stream(stream.map(Ok));
+    let mut groups = Box::pin(readable.group_by(|chunk| chunk.as_f64().unwrap() as u32 % 2));
+
+    sink.send(JsValue::from(1)).await.unwrap();
+    sink.send(JsValue::from(2)).await.unwrap();
+    let mut odd = groups.next().await.unwrap().1.into_stream();
+    let mut even = groups.next().await.unwrap().1.into_stream();
+    assert_eq!(odd.next().await, Some(Ok(JsValue::from(1))));
+    assert_eq!(even.next().await, Some(Ok(JsValue::from(2))));
+
+    // Start a pending read on both groups, the even group reads from the source last
+    let mut odd_next = odd.next().boxed_local();
+    assert!(poll!(&mut odd_next).is_pending());
+    sleep(Duration::from_millis(10)).await;
+    let mut even_next = even.next().boxed_local();
+    assert!(poll!(&mut even_next).is_pending());
+    sleep(Duration::from_millis(10)).await;
+
+    // Dropping the even group must not leave the odd group hanging
+    drop(even_next);
+    drop(even);
+    sleep(Duration::from_millis(10)).await;
+    sink.send(JsValue::from(3)).await.unwrap();
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(
+        poll!(&mut odd_next),
+        Poll::Ready(Some(Ok(JsValue::from(3))))
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_unzip() {
+    let pairs = vec![
+        js_sys::Array::of2(&JsValue::from(1), &JsValue::from("a")).into(),
+        js_sys::Array::of2(&JsValue::from(2), &JsValue::from("b")).into(),
+    ];
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(pairs.into_boxed_slice()));
+
+    let (left, right) = readable.unzip();
+    let (left, right) = join(
+        left.into_stream().try_collect::<Vec<_>>(),
+        right.into_stream().try_collect::<Vec<_>>(),
+    )
+    .await;
+    assert_eq!(left.unwrap(), vec![JsValue::from(1), JsValue::from(2)]);
+    assert_eq!(right.unwrap(), vec![JsValue::from("a"), JsValue::from("b")]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_reader_has_pending_reads() {
+    let mut readable = ReadableStream::from_raw(new_noop_readable_stream());
+    let mut reader = readable.get_reader();
+    assert!(!reader.has_pending_reads());
+
+    // Start a read that never completes, and interrupt it
+    let mut fut = reader.read().boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    drop(fut);
+    assert!(reader.has_pending_reads());
+
+    reader.cancel().await.unwrap();
+    assert!(!reader.has_pending_reads());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_drain() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ));
+    let raw = readable.as_raw().clone();
+
+    readable.drain().await.unwrap();
+
+    // All chunks must be read, and the stream must be closed
+    let mut readable = ReadableStream::from_raw(raw);
+    assert!(!readable.is_locked());
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), None);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_distinct_by() {
+    let new_object = |id: u32, name: &str| -> JsValue {
+        let object = js_sys::Object::new();
+        js_sys::Reflect::set(&object, &"id".into(), &id.into()).unwrap();
+        js_sys::Reflect::set(&object, &"name".into(), &name.into()).unwrap();
+        object.into()
+    };
+    let readable = ReadableStream::from_stream(iter(
+        vec![
+            new_object(1, "a"),
+            new_object(1, "b"),
+            new_object(2, "c"),
+            new_object(1, "d"),
+        ]
+        .into_iter()
+        .map(Ok),
+    ));
+
+    let names = readable
+        .distinct_by(|chunk| js_sys::Reflect::get(chunk, &"id".into()).unwrap().as_f64())
+        .into_stream()
+        .map_ok(|chunk| {
+            js_sys::Reflect::get(&chunk, &"name".into())
+                .unwrap()
+                .as_string()
+                .unwrap()
+        })
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(names, vec!["a", "c", "d"]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_events() {
+    let target = new_counting_event_target();
+    let mut readable = ReadableStream::from_events(&target, "ping");
+    assert_eq!(get_event_listener_count(&target), 1);
+
+    let first = web_sys::Event::new("ping").unwrap();
+    target.dispatch_event(&first).unwrap();
+    // Events of other types must be ignored
+    target
+        .dispatch_event(&web_sys::Event::new("pong").unwrap())
+        .unwrap();
+    let second = web_sys::Event::new("ping").unwrap();
+    target.dispatch_event(&second).unwrap();
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(first.into()));
+    assert_eq!(reader.read().await.unwrap(), Some(second.into()));
+
+    // Canceling must remove the event listener
+    reader.cancel().await.unwrap();
+    assert_eq!(get_event_listener_count(&target), 0);
+    // Dispatching afterwards must not call into the dropped closure
+    target
+        .dispatch_event(&web_sys::Event::new("ping").unwrap())
+        .unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_gate() {
+    let opened = Rc::new(Cell::new(false));
+    let ready = {
+        let opened = opened.clone();
+        async move {
+            sleep(Duration::from_millis(50)).await;
+            opened.set(true);
+        }
+    };
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ))
+    .gate(ready);
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    // No chunks may be emitted before the gate opens
+    assert!(opened.get());
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("world!")));
+    assert_eq!(reader.read().await.unwrap(), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_flatten() {
+    let first = new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    );
+    let second = new_readable_stream_from_array(vec![JsValue::from("Goodbye")].into_boxed_slice());
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![first.into(), second.into()].into_boxed_slice(),
+    ));
+
+    let output = readable
+        .flatten()
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![
+            JsValue::from("Hello"),
+            JsValue::from("world!"),
+            JsValue::from("Goodbye")
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_buffer_all() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ));
+
+    let replay = readable.buffer_all().await.unwrap();
+    let (left, right) = replay.tee();
+    let expected = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let (left, right) = join(
+        left.into_stream().try_collect::<Vec<_>>(),
+        right.into_stream().try_collect::<Vec<_>>(),
+    )
+    .await;
+    assert_eq!(left.unwrap(), expected);
+    assert_eq!(right.unwrap(), expected);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_buffer_all_error() {
+    let error = JsValue::from_str("oops");
+    let readable =
+        ReadableStream::from_stream(iter(vec![Ok(JsValue::from("Hello")), Err(error.clone())]));
+
+    assert_eq!(readable.buffer_all().await.unwrap_err(), error);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_pull_fn() {
+    let mut counter = 0;
+    let mut readable = ReadableStream::from_pull_fn(move || {
+        counter += 1;
+        let chunk = if counter <= 3 {
+            Some(JsValue::from(counter))
+        } else {
+            None
+        };
+        async move { Ok(chunk) }
+    });
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(1)));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(2)));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(3)));
+    assert_eq!(reader.read().await.unwrap(), None);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_with_cleanup() {
+    let cleanups = Rc::new(Cell::new(0));
+    let stream = iter(vec![Ok(JsValue::from("Hello"))]).chain(pending());
+    let mut readable = ReadableStream::from_stream_with_cleanup(stream, {
+        let cleanups = cleanups.clone();
+        move || cleanups.set(cleanups.get() + 1)
+    });
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    assert_eq!(cleanups.get(), 0);
+    reader.cancel().await.unwrap();
+    assert_eq!(cleanups.get(), 1);
+    drop(reader);
+    drop(readable);
+    assert_eq!(cleanups.get(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_with_reader() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello"), JsValue::from("world!")].into_boxed_slice(),
+    ));
+
+    let chunk = readable
+        .with_reader(|mut reader| async move { reader.read().await })
+        .await
+        .unwrap();
+    assert_eq!(chunk, Some(JsValue::from("Hello")));
+
+    // Stream must be unlocked, so the next chunk can be read by another reader
+    assert!(!readable.is_locked());
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("world!")));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_require_nonempty() {
+    let readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![JsValue::from("Hello")].into_boxed_slice(),
+    ));
+    let mut readable = readable.require_nonempty(JsValue::from("empty"));
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    assert_eq!(reader.read().await.unwrap(), None);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_require_nonempty_empty() {
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(vec![].into_boxed_slice()));
+    let mut readable = readable.require_nonempty(JsValue::from("empty"));
+
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await, Err(JsValue::from("empty")));
+    assert_eq!(reader.closed().await, Err(JsValue::from("empty")));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_with_high_water_mark_batches_pulls() {
+    // Every pull runs as a separate task, so count the number of distinct wakers
+    let pulls = Rc::new(Cell::new(0));
+    let mut last_waker: Option<Waker> = None;
+    let mut chunks = 0..10;
+    let stream = poll_fn({
+        let pulls = pulls.clone();
+        move |cx| {
+            if !last_waker.as_ref().is_some_and(|w| w.will_wake(cx.waker())) {
+                pulls.set(pulls.get() + 1);
+                last_waker = Some(cx.waker().clone());
+            }
+            Poll::Ready(chunks.next().map(|i| Ok(JsValue::from(i))))
+        }
+    });
+    let readable = ReadableStream::from_stream_with_high_water_mark(stream, 10.0);
+
+    let output = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(output, (0..10).map(JsValue::from).collect::<Vec<_>>());
+    assert!(pulls.get() < 10, "expected fewer pulls than chunks");
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_with_high_water_mark_error_after_chunks() {
+    // All items are ready right away, so they are enqueued in a single pull
+    let stream = iter(vec![
+        Ok(JsValue::from("Hello")),
+        Ok(JsValue::from("world!")),
+        Err(JsValue::from("oops")),
+    ]);
+    let mut readable = ReadableStream::from_stream_with_high_water_mark(stream, 10.0);
+
+    // The chunks enqueued before the error must still be delivered
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("Hello")));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("world!")));
+    assert_eq!(reader.read().await, Err(JsValue::from("oops")));
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_reconnecting() {
+    let mut attempt = 0;
+    let readable = ReadableStream::reconnecting(
+        move || {
+            attempt += 1;
+            if attempt == 1 {
+                // First connection drops after one chunk
+                ReadableStream::from_stream(iter(vec![
+                    Ok(JsValue::from(1)),
+                    Err(JsValue::from("connection lost")),
+                ]))
+            } else {
+                ReadableStream::from_stream(iter(vec![Ok(JsValue::from(2)), Ok(JsValue::from(3))]))
+            }
+        },
+        3,
+        1,
+    );
+
+    let output = readable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![JsValue::from(1), JsValue::from(2), JsValue::from(3)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_sample() {
+    let (mut sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let readable = ReadableStream::from_stream(stream.map(Ok));
+    let mut output = readable.sample(20).into_stream();
+
+    // Only the last chunk of a burst is emitted on the next tick
+    sink.send(JsValue::from(1)).await.unwrap();
+    sink.send(JsValue::from(2)).await.unwrap();
+    sink.send(JsValue::from(3)).await.unwrap();
+    assert_eq!(output.next().await, Some(Ok(JsValue::from(3))));
+
+    // Nothing is emitted for ticks without new chunks
+    let mut next = output.next();
+    sleep(Duration::from_millis(50)).await;
+    assert!(matches!(poll!(&mut next), Poll::Pending));
+    drop(next);
+
+    sink.send(JsValue::from(4)).await.unwrap();
+    sink.send(JsValue::from(5)).await.unwrap();
+    assert_eq!(output.next().await, Some(Ok(JsValue::from(5))));
+
+    sink.close().await.unwrap();
+    assert_eq!(output.next().await, None);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_default_reader_read_bytes() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let mut reader = readable.get_reader();
+    let mut dst = [0u8; 2];
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 2);
+    assert_eq!(&dst, &[1, 2]);
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 1);
+    assert_eq!(&dst[..1], &[3]);
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 2);
+    assert_eq!(&dst, &[4, 5]);
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 1);
+    assert_eq!(&dst[..1], &[6]);
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 0);
+    reader.closed().await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_default_reader_forget_with_pending_bytes() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let mut reader = readable.get_reader();
+    let mut dst = [0u8; 1];
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 1);
+    assert_eq!(&dst, &[1]);
+
+    // The leftover bytes of the first chunk must be handed out before forgetting
+    let pending = reader.take_pending_bytes().unwrap();
+    assert_eq!(pending.to_vec(), [2, 3]);
+    assert!(reader.take_pending_bytes().is_none());
+
+    // The stream must continue with the next chunk
+    reader.forget().release_lock();
+    let mut reader = readable.get_reader();
+    let chunk = reader.read().await.unwrap().unwrap();
+    assert_eq!(chunk.unchecked_into::<Uint8Array>().to_vec(), [4, 5, 6]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_default_reader_forget_discards_pending_bytes() {
+    let mut readable = ReadableStream::from_raw(new_readable_stream_from_array(
+        vec![
+            Uint8Array::from(&[1, 2, 3][..]).into(),
+            Uint8Array::from(&[4, 5, 6][..]).into(),
+        ]
+        .into_boxed_slice(),
+    ));
+
+    let mut reader = readable.get_reader();
+    let mut dst = [0u8; 1];
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 1);
+
+    // Without taking them first, the leftover bytes are discarded
+    reader.forget().release_lock();
+    let mut reader = readable.get_reader();
+    assert_eq!(reader.read_bytes(&mut dst).await.unwrap(), 1);
+    assert_eq!(&dst, &[4]);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_as_async_iterable() {
+    let chunks = vec![JsValue::from("Hello"), JsValue::from("world!")];
+    let via_iterable = ReadableStream::from_stream_as_async_iterable(iter(chunks.clone()).map(Ok));
+    let via_source = ReadableStream::from_stream(iter(chunks.clone()).map(Ok));
+
+    let output_iterable = via_iterable
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    let output_source = via_source
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(output_iterable, chunks);
+    assert_eq!(output_source, chunks);
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_from_stream_as_async_iterable_cancel_pending_read() {
+    let (stream, observer) = observe_drop(pending());
+    let mut readable = ReadableStream::from_stream_as_async_iterable(stream);
+    let mut reader = readable.get_reader();
+
+    // Start a read that never completes
+    let mut fut = reader.read().boxed_local();
+    assert!(poll!(&mut fut).is_pending());
+    drop(fut);
+    sleep(Duration::from_millis(10)).await;
+    assert!(!observer.is_dropped());
+
+    // Canceling must drop the Rust stream, even while the read is pending
+    reader.cancel().await.unwrap();
+    sleep(Duration::from_millis(10)).await;
+    assert!(observer.is_dropped());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_close_on_idle() {
+    let stream = iter(vec![Ok(JsValue::from("Hello"))]).chain(pending());
+    let (stream, observer) = observe_drop(stream);
+    let readable = ReadableStream::from_stream(stream);
+    let mut output = readable.close_on_idle(20).into_stream();
+
+    assert_eq!(output.next().await, Some(Ok(JsValue::from("Hello"))));
+    // The source never produces another chunk, so the stream closes after the idle period
+    assert_eq!(output.next().await, None);
+    // The source must be canceled, which drops the pending pull in the background
+    sleep(Duration::from_millis(0)).await;
+    assert!(observer.is_dropped());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_map_async() {
+    let chunks = (0..3).map(JsValue::from).collect::<Vec<_>>();
+    let readable =
+        ReadableStream::from_raw(new_readable_stream_from_array(chunks.into_boxed_slice()));
+
+    // Later chunks finish first
+    let output = readable
+        .map_async(3, |chunk| async move {
+            let i = chunk.as_f64().unwrap() as u32;
+            sleep(Duration::from_millis(30 - 10 * u64::from(i))).await;
+            Ok(JsValue::from(i * 10))
+        })
+        .into_stream()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        vec![JsValue::from(0), JsValue::from(10), JsValue::from(20)]
+    );
+}
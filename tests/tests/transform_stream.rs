@@ -1,5 +1,10 @@
+use std::task::Poll;
+
 use futures_util::future::join;
+use futures_util::{poll, FutureExt, SinkExt, TryStreamExt};
+use js_sys::Uint8Array;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 
 use wasm_streams::transform::*;
@@ -49,3 +54,109 @@ async fn test_transform_stream_new_uppercase() {
     )
     .await;
 }
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_into_readable_writable() {
+    let transform = TransformStream::from_raw(new_uppercase_transform_stream());
+    let (mut readable, mut writable) = transform.into_readable_writable();
+    join(
+        async {
+            let mut writer = writable.get_writer();
+            writer.write(JsValue::from("Hello")).await.unwrap();
+            writer.close().await.unwrap();
+        },
+        async {
+            let mut reader = readable.get_reader();
+            assert_eq!(reader.read().await.unwrap(), Some(JsValue::from("HELLO")));
+            assert_eq!(reader.read().await.unwrap(), None);
+        },
+    )
+    .await;
+}
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_into_codec() {
+    let transform = TransformStream::from_raw(new_noop_transform_stream());
+    let raw_transform = transform.as_raw().clone();
+    let (mut sink, stream) = transform.into_codec();
+
+    // Both sides must be locked
+    assert!(raw_transform.writable().locked());
+    assert!(raw_transform.readable().locked());
+
+    let (_, output) = join(
+        async {
+            sink.send(Uint8Array::from(&[1, 2, 3][..]).into())
+                .await
+                .unwrap();
+            sink.send(Uint8Array::from(&[4, 5][..]).into())
+                .await
+                .unwrap();
+            sink.close().await.unwrap();
+        },
+        stream
+            .map_ok(|chunk| chunk.dyn_into::<Uint8Array>().unwrap().to_vec())
+            .try_collect::<Vec<_>>(),
+    )
+    .await;
+    assert_eq!(output.unwrap(), vec![vec![1, 2, 3], vec![4, 5]]);
+}
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_new_with_strategies() {
+    let writable_strategy = web_sys::QueuingStrategy::new();
+    writable_strategy.set_high_water_mark(0.0);
+    let readable_strategy = web_sys::QueuingStrategy::new();
+    readable_strategy.set_high_water_mark(2.0);
+    let transform = TransformStream::new_with_strategies(
+        &js_sys::Object::new(),
+        &writable_strategy,
+        &readable_strategy,
+    )
+    .unwrap();
+
+    let mut writable = transform.writable();
+    let mut writer = writable.get_writer();
+    let mut readable = transform.readable();
+    let mut reader = readable.get_reader();
+
+    // The readable side buffers up to 2 chunks
+    writer.write(JsValue::from(1)).await.unwrap();
+    writer.write(JsValue::from(2)).await.unwrap();
+
+    // Any further writes must wait until the readable side is read from
+    let mut fut = writer.write(JsValue::from(3)).boxed_local();
+    assert!(matches!(poll!(&mut fut), Poll::Pending));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(1)));
+    fut.await.unwrap();
+
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(2)));
+    assert_eq!(reader.read().await.unwrap(), Some(JsValue::from(3)));
+}
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_typed() {
+    let transform = TypedTransformStream::<String, String>::new(
+        TransformStream::from_raw(new_uppercase_transform_stream()),
+        JsValue::from,
+        |chunk| {
+            chunk
+                .as_string()
+                .ok_or_else(|| JsValue::from("expected a string"))
+        },
+    );
+    let (mut sink, stream) = transform.into_codec();
+    let (_, output) = join(
+        async {
+            sink.send("Hello".to_string()).await.unwrap();
+            sink.send("world!".to_string()).await.unwrap();
+            sink.close().await.unwrap();
+        },
+        stream.try_collect::<Vec<_>>(),
+    )
+    .await;
+    assert_eq!(
+        output.unwrap(),
+        vec!["HELLO".to_string(), "WORLD!".to_string()]
+    );
+}
@@ -1,7 +1,12 @@
+use std::cell::RefCell;
 use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use futures_util::stream::iter;
-use futures_util::{AsyncReadExt, AsyncWriteExt, SinkExt, StreamExt};
+use futures_util::{poll, AsyncReadExt, AsyncWriteExt, FutureExt, Sink, SinkExt, StreamExt};
+use gloo_timers::future::sleep;
 use js_sys::Uint8Array;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -54,6 +59,28 @@ fn test_writable_stream_into_sink_impl_unpin() {
     let _ = Pin::new(&sink); // must be Unpin for this to work
 }
 
+#[wasm_bindgen_test]
+async fn test_writable_stream_writer_abort_and_wait() {
+    let recording_stream = RecordingWritableStream::new();
+    let mut writable = WritableStream::from_raw(recording_stream.stream());
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    let reason = JsValue::from("stop");
+    assert_eq!(writer.abort_and_wait(Some(&reason)).await, Ok(()));
+
+    // The stream must be torn down
+    assert_eq!(writer.closed().await, Err(reason.clone()));
+    assert_eq!(writer.desired_size(), None);
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(JsValue::from("Hello")),
+            RecordedEvent::Abort(reason)
+        ]
+    );
+}
+
 #[wasm_bindgen_test]
 async fn test_writable_stream_writer_into_sink() {
     let recording_stream = RecordingWritableStream::new();
@@ -179,6 +206,27 @@ async fn test_writable_stream_into_async_write() {
     );
 }
 
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_async_write_counted() {
+    let (mut writable, counter) = WritableStream::from_async_write_counted(Vec::<u8>::new());
+    assert_eq!(counter.total(), None);
+
+    let mut writer = writable.get_writer();
+    writer
+        .write(Uint8Array::from(&[1, 2, 3][..]).into())
+        .await
+        .unwrap();
+    writer
+        .write(Uint8Array::from(&[4, 5][..]).into())
+        .await
+        .unwrap();
+    assert_eq!(counter.bytes_written(), 5);
+    assert_eq!(counter.total(), None);
+
+    writer.close().await.unwrap();
+    assert_eq!(counter.total(), Some(5));
+}
+
 #[wasm_bindgen_test]
 fn test_writable_stream_into_async_write_impl_unpin() {
     let writable = WritableStream::from_raw(new_noop_writable_stream());
@@ -282,3 +330,675 @@ async fn test_writable_stream_from_async_write() {
     assert_eq!(async_read.read_to_end(&mut dest).await.unwrap(), 6);
     assert_eq!(dest, [1, 2, 3, 4, 5, 6]);
 }
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_sink_write_after_close() {
+    let recording_stream = RecordingWritableStream::new();
+    let writable = WritableStream::from_raw(recording_stream.stream());
+    let mut sink = writable.into_sink();
+
+    assert_eq!(sink.send(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(sink.close().await, Ok(()));
+
+    // Writing after close must fail
+    assert!(sink.send(JsValue::from("world!")).await.is_err());
+
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(JsValue::from("Hello")),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_sink_write_after_error() {
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| async {
+        Err::<(), JsValue>(JsValue::from("oops"))
+    });
+    let writable = WritableStream::from_sink(sink);
+    let mut sink = writable.into_sink();
+
+    assert_eq!(
+        sink.send(JsValue::from("Hello")).await,
+        Err(JsValue::from("oops"))
+    );
+
+    // Writing or closing after an error must report the stream's error
+    assert_eq!(
+        sink.send(JsValue::from("world!")).await,
+        Err(JsValue::from("oops"))
+    );
+    assert_eq!(sink.close().await, Err(JsValue::from("oops")));
+}
+
+#[cfg(all(feature = "panic-capture", panic = "unwind"))]
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_panic_capture() {
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| async {
+        panic!("oh no") as Result<(), JsValue>
+    });
+    let mut writable = WritableStream::from_sink(sink);
+
+    let mut writer = writable.get_writer();
+    // The panic must error the stream
+    let err = writer.write(JsValue::from("Hello")).await.unwrap_err();
+    let err = err.dyn_into::<js_sys::Error>().unwrap();
+    assert!(err.message().as_string().unwrap().contains("oh no"));
+}
+
+#[cfg(not(all(feature = "panic-capture", panic = "unwind")))]
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_without_panic_capture() {
+    // Without panic capture, errors must pass through unchanged
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| async {
+        Err::<(), JsValue>(JsValue::from("oops"))
+    });
+    let mut writable = WritableStream::from_sink(sink);
+
+    let mut writer = writable.get_writer();
+    assert_eq!(
+        writer.write(JsValue::from("Hello")).await,
+        Err(JsValue::from("oops"))
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_desired_size_updates() {
+    let recording_stream = RecordingWritableStream::new();
+    let mut writable = WritableStream::from_raw(recording_stream.stream());
+    let mut writer = writable.get_writer();
+    let mut updates = writer.desired_size_updates();
+
+    // Initially ready
+    assert_eq!(updates.next().await, Some(Some(1.0)));
+
+    // Writing applies backpressure until the sink has processed the chunk
+    writer.write(JsValue::from("Hello")).await.unwrap();
+    assert_eq!(updates.next().await, Some(Some(1.0)));
+    writer.write(JsValue::from("world!")).await.unwrap();
+    assert_eq!(updates.next().await, Some(Some(1.0)));
+
+    // Closing yields the final desired size
+    writer.close().await.unwrap();
+    assert_eq!(updates.next().await, Some(Some(0.0)));
+    assert_eq!(updates.next().await, None);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_async_write_with_name() {
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| async {
+        Err::<(), JsValue>(JsValue::from_str("oops"))
+    });
+    let writable = WritableStream::from_sink(sink);
+
+    let mut async_write = writable.into_async_write().with_name("upload");
+    async_write.write_all(&[1, 2, 3]).await.unwrap();
+    let err = async_write.flush().await.unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("upload: "), "{}", message);
+    assert!(message.contains("oops"), "{}", message);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_with_label() {
+    let writable = WritableStream::from_raw(new_noop_writable_stream()).with_label("upload");
+    let debug = format!("{:?}", writable);
+    assert!(debug.contains("upload"), "{}", debug);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_sink_release() {
+    let recording_stream = RecordingWritableStream::new();
+    let mut writable = WritableStream::from_raw(recording_stream.stream());
+
+    let mut sink = writable.get_writer().into_sink();
+    assert_eq!(sink.send(JsValue::from("Hello")).await, Ok(()));
+
+    // Releasing the sink should release the lock, without closing the stream
+    sink.release();
+    assert!(!writable.is_locked());
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(JsValue::from("Hello")),
+            RecordedEvent::Write(JsValue::from("world!")),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_byte_sink() {
+    let recording_stream = RecordingWritableStream::new();
+    let writable = WritableStream::from_raw(recording_stream.stream());
+
+    let mut sink = Box::pin(writable.into_byte_sink());
+    sink.send(vec![1, 2, 3]).await.unwrap();
+    sink.send(vec![4, 5]).await.unwrap();
+    sink.close().await.unwrap();
+
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(Uint8Array::from(&[1, 2, 3][..]).into()),
+            RecordedEvent::Write(Uint8Array::from(&[4, 5][..]).into()),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_utf8_sink() {
+    let recording_stream = RecordingWritableStream::new();
+    let writable = WritableStream::from_raw(recording_stream.stream());
+
+    let mut sink = Box::pin(writable.into_utf8_sink());
+    sink.send("Hi".to_string()).await.unwrap();
+    sink.send("été".to_string()).await.unwrap();
+    sink.close().await.unwrap();
+
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(Uint8Array::from(&[0x48, 0x69][..]).into()),
+            RecordedEvent::Write(Uint8Array::from(&[0xc3, 0xa9, 0x74, 0xc3, 0xa9][..]).into()),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_pipelined() {
+    let started = Rc::new(RefCell::new(Vec::new()));
+    let acked = Rc::new(RefCell::new(Vec::new()));
+    let mut writable = WritableStream::from_pipelined(
+        {
+            let started = started.clone();
+            let acked = acked.clone();
+            move |chunk: JsValue| {
+                started.borrow_mut().push(chunk.clone());
+                let acked = acked.clone();
+                async move {
+                    // Later chunks are acknowledged sooner
+                    let delay = 30 - 10 * chunk.as_f64().unwrap() as u64;
+                    sleep(Duration::from_millis(delay)).await;
+                    acked.borrow_mut().push(chunk);
+                    Ok(())
+                }
+            }
+        },
+        3,
+    );
+
+    let mut writer = writable.get_writer();
+    for i in 0..3 {
+        writer.write(JsValue::from(i)).await.unwrap();
+    }
+    writer.close().await.unwrap();
+
+    assert_eq!(
+        *started.borrow(),
+        [JsValue::from(0), JsValue::from(1), JsValue::from(2)]
+    );
+    assert_eq!(
+        *acked.borrow(),
+        [JsValue::from(2), JsValue::from(1), JsValue::from(0)]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_recoverable() {
+    let sink = SimpleChannel::<JsValue>::new().sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let (mut writable, recover) = WritableStream::from_sink_recoverable(sink);
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    // Recover the sink and reuse it for a new stream
+    let sink = recover.await.unwrap();
+    let (mut writable, recover) = WritableStream::from_sink_recoverable(sink);
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    let channel = recover.await.unwrap().into_inner();
+    let output = channel.collect::<Vec<_>>().await;
+    assert_eq!(
+        output,
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_recoverable_abort() {
+    let sink = SimpleChannel::<JsValue>::new().sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let (mut writable, recover) = WritableStream::from_sink_recoverable(sink);
+    assert_eq!(writable.abort().await, Ok(()));
+    assert!(recover.await.is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_async_write_drop_without_flush() {
+    let recording_stream = RecordingWritableStream::new();
+    let mut writable = WritableStream::from_raw(recording_stream.stream());
+
+    {
+        let mut async_write = writable.get_writer().into_async_write();
+        assert_eq!(async_write.write(&[1, 2, 3]).await.unwrap(), 3);
+        // Drop without flushing
+    }
+    assert!(!writable.is_locked());
+
+    // The queued write must still reach the stream
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.close().await, Ok(()));
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(Uint8Array::from(&[1, 2, 3][..]).into()),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_write_timeout() {
+    // A sink that never completes a write
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| {
+        futures_util::future::pending::<Result<(), JsValue>>()
+    });
+    let mut writable = WritableStream::from_sink_with_write_timeout(sink, 10);
+    let mut writer = writable.get_writer();
+
+    let error = writer.write(JsValue::from("Hello")).await.unwrap_err();
+    assert!(error.is_instance_of::<js_sys::Error>());
+    assert!(writer.closed().await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_options() {
+    let (sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let reasons = Rc::new(RefCell::new(Vec::new()));
+    let options = SinkOptions::new()
+        .write_timeout(1000)
+        .retry(3, |_| true)
+        .abort_handler({
+            let reasons = reasons.clone();
+            move |reason| async move {
+                reasons.borrow_mut().push(reason);
+                Ok(())
+            }
+        });
+    let mut writable = WritableStream::from_sink_with_options(sink, options);
+
+    // All options must apply together
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    drop(writer);
+    let result = writable.abort_with_reason(&JsValue::from("stop")).await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(*reasons.borrow(), vec![JsValue::from("stop")]);
+
+    // The chunk must have been sent exactly once
+    let output = stream.collect::<Vec<_>>().await;
+    assert_eq!(output, vec![JsValue::from("Hello")]);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_options_timeout_with_retry() {
+    // A sink that never completes a write
+    let sink = futures_util::sink::unfold((), |(), _chunk: JsValue| {
+        futures_util::future::pending::<Result<(), JsValue>>()
+    });
+    let options = SinkOptions::new().write_timeout(10).retry(3, |_| true);
+    let mut writable = WritableStream::from_sink_with_options(sink, options);
+    let mut writer = writable.get_writer();
+
+    // The timeout must still apply when retries are enabled
+    let error = writer.write(JsValue::from("Hello")).await.unwrap_err();
+    assert!(error.is_instance_of::<js_sys::Error>());
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_writer_write_all_bytes() {
+    let recording_stream = RecordingWritableStream::new_with_byte_high_water_mark(16.0);
+    let mut writable = WritableStream::from_raw(recording_stream.stream());
+    let mut writer = writable.get_writer();
+
+    let data = (0..100).collect::<Vec<u8>>();
+    writer.write_all_bytes(&data).await.unwrap();
+    writer.close().await.unwrap();
+
+    // All bytes must be written in chunks that fit in the queue
+    let mut events = recording_stream.events();
+    assert_eq!(events.pop(), Some(RecordedEvent::Close));
+    let mut output = Vec::new();
+    for event in events {
+        match event {
+            RecordedEvent::Write(chunk) => {
+                let chunk = chunk.unchecked_into::<Uint8Array>().to_vec();
+                assert!(!chunk.is_empty() && chunk.len() <= 16);
+                output.extend(chunk);
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+    assert_eq!(output, data);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_into_async_write_empty() {
+    let recording_stream = RecordingWritableStream::new();
+    let writable = WritableStream::from_raw(recording_stream.stream());
+
+    let mut async_write = writable.into_async_write();
+    assert_eq!(async_write.write(&[]).await.unwrap(), 0);
+    assert_eq!(async_write.write(&[1, 2, 3]).await.unwrap(), 3);
+    async_write.close().await.unwrap();
+
+    // Empty writes must not produce a chunk
+    assert_eq!(
+        recording_stream.events(),
+        [
+            RecordedEvent::Write(Uint8Array::from(&[1, 2, 3][..]).into()),
+            RecordedEvent::Close
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_transform() {
+    let (sink, stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let mut writable = WritableStream::from_sink_with_transform(sink, |chunk| {
+        let chunk = chunk
+            .as_string()
+            .ok_or_else(|| JsValue::from("not a string"))?;
+        Ok(JsValue::from(chunk.to_uppercase()))
+    });
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    let output = stream.collect::<Vec<_>>().await;
+    assert_eq!(
+        output,
+        vec![JsValue::from("HELLO"), JsValue::from("WORLD!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_validated() {
+    let (sink, mut stream) = SimpleChannel::<JsValue>::new().split();
+    let sink = sink.sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let mut writable = WritableStream::from_sink_validated(sink, |chunk| {
+        if chunk.is_instance_of::<Uint8Array>() {
+            Ok(())
+        } else {
+            Err(JsValue::from("not a Uint8Array"))
+        }
+    });
+
+    let mut writer = writable.get_writer();
+    let chunk = JsValue::from(Uint8Array::from(&[1, 2, 3][..]));
+    assert_eq!(writer.write(chunk.clone()).await, Ok(()));
+    assert_eq!(
+        writer.write(JsValue::from("Hello")).await,
+        Err(JsValue::from("not a Uint8Array"))
+    );
+    assert_eq!(
+        writer.closed().await,
+        Err(JsValue::from("not a Uint8Array"))
+    );
+
+    // Only the valid chunk reached the sink
+    assert_eq!(stream.next().await, Some(chunk));
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_write_fn() {
+    let chunks = Rc::new(RefCell::new(Vec::new()));
+    let mut writable = WritableStream::from_write_fn({
+        let chunks = chunks.clone();
+        move |chunk| {
+            let chunks = chunks.clone();
+            async move {
+                sleep(Duration::from_millis(10)).await;
+                chunks.borrow_mut().push(chunk);
+                Ok(())
+            }
+        }
+    });
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    assert_eq!(
+        *chunks.borrow(),
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_autoflush() {
+    // A sink that only passes on its chunks when flushed
+    struct BufferedSink {
+        buffer: Vec<JsValue>,
+        flushed: Rc<RefCell<Vec<JsValue>>>,
+    }
+    impl Sink<JsValue> for BufferedSink {
+        type Error = JsValue;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), JsValue> {
+            self.buffer.push(item);
+            Ok(())
+        }
+
+        fn poll_flush(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), JsValue>> {
+            let buffer = std::mem::take(&mut self.buffer);
+            self.flushed.borrow_mut().extend(buffer);
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            self.poll_flush(cx)
+        }
+    }
+
+    let flushed = Rc::new(RefCell::new(Vec::new()));
+    let sink = BufferedSink {
+        buffer: Vec::new(),
+        flushed: flushed.clone(),
+    };
+    let mut writable = WritableStream::from_sink_autoflush(sink, 20);
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    // Writes must not be flushed right away
+    assert!(flushed.borrow().is_empty());
+
+    // Writes must be flushed by the timer
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(
+        *flushed.borrow(),
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+
+    assert_eq!(writer.write(JsValue::from("Goodbye")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+    assert_eq!(flushed.borrow().len(), 3);
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_retry() {
+    // A sink that fails the first attempt of every write
+    struct FlakySink {
+        chunks: Rc<RefCell<Vec<JsValue>>>,
+        attempts: usize,
+    }
+    impl Sink<JsValue> for FlakySink {
+        type Error = JsValue;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, item: JsValue) -> Result<(), JsValue> {
+            self.attempts += 1;
+            if self.attempts % 2 == 1 {
+                return Err(JsValue::from("transient"));
+            }
+            self.chunks.borrow_mut().push(item);
+            Ok(())
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let chunks = Rc::new(RefCell::new(Vec::new()));
+    let sink = FlakySink {
+        chunks: chunks.clone(),
+        attempts: 0,
+    };
+    let mut writable = WritableStream::from_sink_with_retry(sink, 1, |err| {
+        err.as_string().as_deref() == Some("transient")
+    });
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    assert_eq!(
+        *chunks.borrow(),
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_retry_flush() {
+    // A sink that fails the first attempt of every flush
+    struct FlakyFlushSink {
+        chunks: Rc<RefCell<Vec<JsValue>>>,
+        flushes: usize,
+    }
+    impl Sink<JsValue> for FlakyFlushSink {
+        type Error = JsValue;
+
+        fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: JsValue) -> Result<(), JsValue> {
+            self.chunks.borrow_mut().push(item);
+            Ok(())
+        }
+
+        fn poll_flush(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<(), JsValue>> {
+            self.flushes += 1;
+            if self.flushes % 2 == 1 {
+                return Poll::Ready(Err(JsValue::from("transient")));
+            }
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), JsValue>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let chunks = Rc::new(RefCell::new(Vec::new()));
+    let sink = FlakyFlushSink {
+        chunks: chunks.clone(),
+        flushes: 0,
+    };
+    let mut writable = WritableStream::from_sink_with_retry(sink, 1, |err| {
+        err.as_string().as_deref() == Some("transient")
+    });
+
+    let mut writer = writable.get_writer();
+    assert_eq!(writer.write(JsValue::from("Hello")).await, Ok(()));
+    assert_eq!(writer.write(JsValue::from("world!")).await, Ok(()));
+    assert_eq!(writer.close().await, Ok(()));
+
+    // Retrying the flush must not send the chunks again
+    assert_eq!(
+        *chunks.borrow(),
+        vec![JsValue::from("Hello"), JsValue::from("world!")]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_wait_ready() {
+    let written = Rc::new(RefCell::new(Vec::new()));
+    let mut writable = WritableStream::from_write_fn({
+        let written = written.clone();
+        move |chunk| {
+            let written = written.clone();
+            async move {
+                sleep(Duration::from_millis(50)).await;
+                written.borrow_mut().push(chunk);
+                Ok(())
+            }
+        }
+    });
+
+    // Start a slow write, filling up the queue
+    {
+        let mut writer = writable.get_writer();
+        let mut write = writer.write(JsValue::from("Hello")).boxed_local();
+        assert!(poll!(&mut write).is_pending());
+    }
+    assert!(!writable.is_locked());
+
+    // Must wait until the write has completed
+    writable.wait_ready().await.unwrap();
+    assert_eq!(*written.borrow(), vec![JsValue::from("Hello")]);
+    assert!(!writable.is_locked());
+}
+
+#[wasm_bindgen_test]
+async fn test_writable_stream_from_sink_with_abort_handler() {
+    let sink = SimpleChannel::<JsValue>::new().sink_map_err(|_| JsValue::from_str("cannot happen"));
+    let reasons = Rc::new(RefCell::new(Vec::new()));
+    let mut writable = WritableStream::from_sink_with_abort_handler(sink, {
+        let reasons = reasons.clone();
+        move |reason| async move {
+            reasons.borrow_mut().push(reason);
+            Err(JsValue::from("abort failed"))
+        }
+    });
+
+    let result = writable.abort_with_reason(&JsValue::from("stop")).await;
+    assert_eq!(result, Err(JsValue::from("abort failed")));
+    assert_eq!(*reasons.borrow(), vec![JsValue::from("stop")]);
+}
@@ -3,7 +3,7 @@ use std::pin::Pin;
 use std::rc::Rc;
 use std::task::{Context, Poll};
 
-use futures_util::{Sink, Stream};
+use futures_util::{AsyncRead, Sink, Stream};
 use pin_project::{pin_project, pinned_drop};
 
 #[pin_project(PinnedDrop)]
@@ -75,3 +75,13 @@ where
         self.project().inner.poll_close(cx)
     }
 }
+
+impl<R: AsyncRead> AsyncRead for DropObservable<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+}